@@ -1,102 +1,237 @@
 use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
 use chrono::NaiveDateTime;
-use clap::StructOpt;
 use std::ops::Add;
-use crate::champion_info::ChampionInfo;
-use crate::string::{ indent_string, format_percent, INDENTATION_STRING };
-use crate::win_rate_info::WinRateInfo;
+use crate::champion_info::{get_game_length_name, get_side_name, ChampionInfo, DEFAULT_SUMMARY_LIMIT};
+use crate::champion_class::ChampionClasses;
+use crate::composition::{CompositionInfo, TeamComposition};
+use crate::draft_score::{DraftScore, MatchupScore};
+use crate::error::{Error, Result};
+use crate::match_cache::{get_modified, MatchCache};
+use crate::match_facts::{Abnormality, MatchFacts, ParticipantFacts};
+use crate::match_model::Match;
+use crate::player_info::PlayerInfo;
+use crate::pool::{PoolRecommendation, RoleInfo};
+use crate::progress::create_progress_bar;
+use crate::string::indent_string;
+use crate::win_rate_info::{WinRateInfo, DEFAULT_SIGNIFICANCE_THRESHOLD};
 use edit_distance::edit_distance;
+use rayon::prelude::*;
+use tracing::{debug, info, warn};
 
-const STATISTICAL_SATURATION_THRESHOLD: i32 = 12;
+/// Every class bucket collects games from many different champions, so it is allowed to hold more of them.
+const CLASS_SATURATION_THRESHOLD: i32 = 60;
 
-struct Analyzer {
+/// Match files parsed in parallel before they are counted; small enough that a short duration limit does not parse
+/// much more than it needs.
+const PARSE_BATCH_SIZE: usize = 256;
+
+/// Outcome of one of my matches
+pub struct MatchResult {
+    pub moment: NaiveDateTime,
+    /// Seconds
+    pub game_duration: i64,
+    pub champion_name: String,
+    pub win: bool,
+    /// The stored match file
+    pub file_path: PathBuf,
+}
+
+/// Win rates of the tracked player, built from the stored match files
+pub struct Analyzer {
     duration_limit: chrono::Duration,
+    data_directory: String,
+    show_progress: bool,
+    /// Only this many latest matches are counted for every ally and enemy
+    saturation_threshold: i32,
+    /// Matches needed before a win rate is trusted as it is
+    significance_threshold: i32,
+    /// Length of every best and worst list in the summary
+    summary_limit: usize,
     summoner_id: String,
+    /// Taken from the latest match I played
+    puuid: Option<String>,
     champion_infos: HashMap<String, ChampionInfo>,
+    champion_classes: ChampionClasses,
+    composition_info: CompositionInfo,
+    /// Other players by puuid
+    player_infos: HashMap<String, PlayerInfo>,
+    /// By team position such as "TOP"
+    role_infos: HashMap<String, RoleInfo>,
+    /// Newest first, like the match files
+    match_results: Vec<MatchResult>,
+    /// Corrupt match files left out of the last analysis
+    skipped_files: Vec<Error>,
+    /// Remakes and AFK games are always left out, early surrenders only when this is set
+    exclude_early_surrenders: bool,
+    /// Count of my matches left out of the last analysis
+    filtered_matches: HashMap<Abnormality, usize>,
 }
 
 impl Analyzer {
-    pub fn new(summoner_id: String) -> Analyzer {
+    pub fn new(data_directory: String, summoner_id: String, champion_classes: ChampionClasses) -> Analyzer {
         return Analyzer {
             duration_limit: chrono::Duration::days(0),
+            data_directory,
+            show_progress: false,
+            saturation_threshold: 12,
+            significance_threshold: DEFAULT_SIGNIFICANCE_THRESHOLD,
+            summary_limit: DEFAULT_SUMMARY_LIMIT,
             summoner_id,
-            champion_infos: HashMap::new()
+            puuid: None,
+            champion_infos: HashMap::new(),
+            champion_classes,
+            composition_info: CompositionInfo::new(),
+            player_infos: HashMap::new(),
+            role_infos: HashMap::new(),
+            match_results: Vec::new(),
+            skipped_files: Vec::new(),
+            exclude_early_surrenders: false,
+            filtered_matches: HashMap::new(),
         }
     }
 
-    pub fn analyze_files(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+    /// Matches older than `duration_limit` before the latest match are ignored
+    pub fn set_duration_limit(&mut self, duration_limit: chrono::Duration) {
+        self.duration_limit = duration_limit;
+    }
+
+    /// Draw a progress bar on stderr while analyzing the match files
+    pub fn set_show_progress(&mut self, show_progress: bool) {
+        self.show_progress = show_progress;
+    }
+
+    pub fn set_saturation_threshold(&mut self, saturation_threshold: i32) {
+        self.saturation_threshold = saturation_threshold;
+    }
+
+    pub fn set_significance_threshold(&mut self, significance_threshold: i32) {
+        self.significance_threshold = significance_threshold;
+    }
+
+    pub fn set_summary_limit(&mut self, summary_limit: usize) {
+        self.summary_limit = summary_limit;
+    }
+
+    pub fn set_exclude_early_surrenders(&mut self, exclude_early_surrenders: bool) {
+        self.exclude_early_surrenders = exclude_early_surrenders;
+    }
+
+    /// Match files are counted one by one, newest first, because the saturation threshold depends on that order.
+    /// Corrupt match files are skipped and listed in `get_skipped_files`
+    pub fn analyze_files(&mut self) -> Result<()> {
         self.champion_infos.clear();
-        let mut files: Vec<std::fs::DirEntry> = std::fs::read_dir("./data")
-            .expect("Data directory is required")
-            .map(|file_path| file_path.expect("A valid file path is required"))
-            .collect();
-        files.sort_by(|a, b| a.file_name().cmp(&b.file_name()).reverse());
-        let mut latest_chronological_date: Option<NaiveDateTime> = None;
-        let mut latest_processed_date: Option<NaiveDateTime> = None;
-        let mut count_of_processed_files = 0;
-        for (i, file_path) in files.iter().enumerate() {
-            match latest_chronological_date {
-                Some(latest_chronological_date) => {
-                    match latest_processed_date {
-                        Some(latest_processed_date) => {
-                            let duration = latest_chronological_date
-                                .signed_duration_since(latest_processed_date);
-                            if duration > self.duration_limit {
-                                println!("Duration limit reached at {}", latest_processed_date);
-                                break;
-                            }
-                        },  _ => {}
-                    }
-                },  _ => {}
+        self.puuid = None;
+        self.composition_info = CompositionInfo::with_significance_threshold(self.significance_threshold);
+        self.player_infos.clear();
+        self.role_infos.clear();
+        self.match_results.clear();
+        self.skipped_files.clear();
+        self.filtered_matches.clear();
+        let data_directory = self.data_directory.clone();
+        let summary = read_match_facts(&data_directory, self.duration_limit, self.show_progress, "Analyzing matches",
+            |facts, moment, file_path| self.add_match_facts(facts, moment, file_path))?;
+        info!(total_files = summary.total_files, processed_files = summary.processed_files,
+            parsed_files = summary.parsed_files, skipped_files = summary.skipped_files.len(),
+            filtered_matches = self.filtered_matches.values().sum::<usize>(), "Analysis complete");
+        self.skipped_files = summary.skipped_files;
+        return Ok(());
+    }
+
+    fn add_match_facts(&mut self, facts: &MatchFacts, moment: NaiveDateTime, file_path: PathBuf) {
+        let participation = match facts.get_participation(&self.summoner_id) {
+            Some(participation) => participation,
+            None => return,
+        };
+        let me = participation.me;
+        let win = me.win;
+        if self.puuid.is_none() && !me.puuid.is_empty() {
+            self.puuid = Some(me.puuid.clone());
+        }
+        if let Some(abnormality) = facts.abnormality {
+            if abnormality != Abnormality::EarlySurrender || self.exclude_early_surrenders {
+                debug!(?abnormality, %moment, "Filtering match");
+                *self.filtered_matches.entry(abnormality).or_default() += 1;
+                return;
             }
-            let file_content = std::fs::read_to_string(file_path.path())?;
-            let match_history: riven::models::match_v5::Match = serde_json::from_str(&file_content)?;
-            let moment = NaiveDateTime::from_timestamp(
-                match_history.info.game_creation / 1000,
-                (match_history.info.game_creation % 1000) as u32);
-            if latest_chronological_date.is_none() {
-                latest_chronological_date = Some(moment);
+        }
+        self.match_results.push(MatchResult {
+            moment,
+            game_duration: facts.game_duration,
+            champion_name: me.champion_name.clone(),
+            win,
+            file_path,
+        });
+        if !me.role.is_empty() {
+            self.role_infos.entry(me.role.clone()).or_default().add(
+                &me.champion_name,
+                participation.enemies.iter().map(|enemy| enemy.champion_name.clone()));
+        }
+        let significance_threshold = self.significance_threshold;
+        let champion_info = self.champion_infos.entry(me.champion_name.clone())
+            .or_insert_with(|| ChampionInfo::with_significance_threshold(significance_threshold));
+        champion_info.count_of_matches += 1;
+        champion_info.get_win_rate_on_side(get_side_name(me.team_id)).add(win);
+        if facts.game_duration > 0 {
+            champion_info.get_win_rate_by_game_length(get_game_length_name(facts.game_duration)).add(win);
+        }
+
+        // My own champion counts among the allies of the champion matchups and the team composition
+        let mut allies: Vec<&ParticipantFacts> = vec![me];
+        allies.extend(&participation.allies);
+        for ally in &allies {
+            let win_rate_info = champion_info.get_win_rate_with(&ally.champion_name);
+            if win_rate_info.get_count_of_matches() < self.saturation_threshold {
+                win_rate_info.add(win);
             }
-            latest_processed_date = Some(moment);
-            if i % 100 == 0 {
-                println!("Analyzing file {} -> {}...", i, moment);
+        }
+        let enemies = &participation.enemies;
+        for enemy in enemies {
+            let win_rate_info = champion_info.get_win_rate_vs(&enemy.champion_name);
+            if win_rate_info.get_count_of_matches() < self.saturation_threshold {
+                win_rate_info.add(win);
             }
-            self.add_match_history(&match_history);
-            count_of_processed_files += 1;
-        };
-        println!("Analysis complete. Total files: {}. Processed files: {}", files.len(), count_of_processed_files);
-        Ok(())
-    }
-
-    fn add_match_history(&mut self, match_history: &riven::models::match_v5::Match) {
-        for participant in &match_history.info.participants {
-            if participant.summoner_id == self.summoner_id {
-                let my_champion = participant.champion_name.clone();
-                let champion_info = self.champion_infos.entry(my_champion).or_insert(ChampionInfo::new());
-                champion_info.count_of_matches += 1;
-
-                let allies = find_participants_by_team_id(&match_history.info, participant.team_id, true);
-                for ally in allies {
-                    let win_rate_info = champion_info.get_win_rate_with(&ally.champion_name);
-                    if win_rate_info.get_count_of_matches() < STATISTICAL_SATURATION_THRESHOLD {
-                        win_rate_info.add(participant.win);
-                    }
-                }
-                let enemies = find_participants_by_team_id(&match_history.info, participant.team_id, false);
-                for enemy in enemies {
-                    let win_rate_info = champion_info.get_win_rate_vs(&enemy.champion_name);
-                    if win_rate_info.get_count_of_matches() < STATISTICAL_SATURATION_THRESHOLD {
-                        win_rate_info.add(participant.win);
-                    }
-                }
+        }
+
+        // Each class counts once per match, and my own champion is not my ally
+        let ally_classes = get_team_classes(&self.champion_classes, participation.allies.iter()
+            .map(|ally| ally.champion_name.as_str()));
+        for class_name in &ally_classes {
+            let win_rate_info = champion_info.get_win_rate_with_class(class_name);
+            if win_rate_info.get_count_of_matches() < CLASS_SATURATION_THRESHOLD {
+                win_rate_info.add(win);
             }
         }
+        let enemy_classes = get_team_classes(&self.champion_classes, enemies.iter()
+            .map(|enemy| enemy.champion_name.as_str()));
+        for class_name in &enemy_classes {
+            let win_rate_info = champion_info.get_win_rate_vs_class(class_name);
+            if win_rate_info.get_count_of_matches() < CLASS_SATURATION_THRESHOLD {
+                win_rate_info.add(win);
+            }
+        }
+
+        let my_team = TeamComposition::new(&allies, &self.champion_classes);
+        let enemy_team = TeamComposition::new(enemies, &self.champion_classes);
+        self.composition_info.add(&my_team, &enemy_team, win);
+
+        // Players are not saturated, the point is to find the ones met again and again
+        for ally in participation.allies.iter().filter(|ally| !ally.puuid.is_empty()) {
+            self.player_infos.entry(ally.puuid.clone())
+                .or_insert_with(|| PlayerInfo::new(ally.name.clone(), significance_threshold))
+                .get_win_rate_with().add(win);
+        }
+        for enemy in enemies.iter().filter(|enemy| !enemy.puuid.is_empty()) {
+            self.player_infos.entry(enemy.puuid.clone())
+                .or_insert_with(|| PlayerInfo::new(enemy.name.clone(), significance_threshold))
+                .get_win_rate_vs().add(win);
+        }
     }
 
-    fn get_sorted_champions(&self) -> Vec<(&String, &ChampionInfo)> {
+    pub fn get_sorted_champions(&self) -> Vec<(&String, &ChampionInfo)> {
         let mut champions: Vec<(&String, &ChampionInfo)> = Vec::new();
         for (champion_name, champion_info) in &self.champion_infos {
-            champions.push((champion_name, &champion_info));
+            champions.push((champion_name, champion_info));
         }
         champions.sort_by(|a, b|
             a.1.count_of_matches.partial_cmp(&b.1.count_of_matches).unwrap().reverse()
@@ -104,98 +239,159 @@ impl Analyzer {
         return champions;
     }
 
-    fn get_summary_text(&self) -> String {
+    pub fn get_champion_infos(&self) -> &HashMap<String, ChampionInfo> {
+        return &self.champion_infos;
+    }
+
+    pub fn get_composition_info(&self) -> &CompositionInfo {
+        return &self.composition_info;
+    }
+
+    pub fn get_skipped_files(&self) -> &[Error] {
+        return &self.skipped_files;
+    }
+
+    /// My puuid as found in the stored matches
+    pub fn get_puuid(&self) -> Option<&str> {
+        return self.puuid.as_deref();
+    }
+
+    /// Other players by puuid
+    pub fn get_player_infos(&self) -> &HashMap<String, PlayerInfo> {
+        return &self.player_infos;
+    }
+
+    pub fn get_player_summary_text(&self) -> String {
+        return PlayerInfo::get_summary_text(self.player_infos.values());
+    }
+
+    /// By team position such as "TOP"
+    pub fn get_role_infos(&self) -> &HashMap<String, RoleInfo> {
+        return &self.role_infos;
+    }
+
+    /// One champion pool per role I played, most played role first
+    pub fn get_pool_recommendations(&self) -> Vec<PoolRecommendation> {
+        let mut roles: Vec<(&String, &RoleInfo)> = self.role_infos.iter().collect();
+        roles.sort_by(|a, b| b.1.count_of_matches.cmp(&a.1.count_of_matches).then_with(|| a.0.cmp(b.0)));
+        return roles.into_iter()
+            .map(|(role, role_info)| PoolRecommendation::new(role, role_info, &self.champion_infos))
+            .collect();
+    }
+
+    pub fn get_match_results(&self) -> &[MatchResult] {
+        return &self.match_results;
+    }
+
+    pub fn get_filtered_matches(&self) -> &HashMap<Abnormality, usize> {
+        return &self.filtered_matches;
+    }
+
+    /// For example "filtered matches: 3 remakes, 1 AFK games", empty when nothing was filtered
+    pub fn get_filtered_summary_text(&self) -> String {
+        let mut filtered_matches: Vec<(&Abnormality, &usize)> = self.filtered_matches.iter().collect();
+        if filtered_matches.is_empty() {
+            return String::new();
+        }
+        filtered_matches.sort();
+        let counts: Vec<String> = filtered_matches.iter()
+            .map(|(abnormality, count)| count.to_string().add(" ").add(abnormality.get_name()))
+            .collect();
+        return String::from("filtered matches: ").add(&counts.join(", ")).add("\n");
+    }
+
+    pub fn get_summary_text(&self) -> String {
         let champions = self.get_sorted_champions();
         let mut text = String::new();
         for champion in champions {
             let (champion_name, champion_info) = champion;
             text = text
                 .add(champion_name).add("\n")
-                .add(&indent_string(&champion_info.get_summary_text()))
+                .add(&indent_string(&champion_info.get_summary_text(self.summary_limit)))
                 .add("\n");
         }
         return text;
     }
 
-    fn get_score_summary_text(&self, allies: Vec<&str>, enemies: Vec<&str>) -> String {
+    pub fn get_score_summary_text(&self, draft_scores: &[DraftScore]) -> String {
         let mut text = String::new();
-        let champions = self.get_sorted_champions();
+        for draft_score in draft_scores {
+            text.push_str(&draft_score.get_summary_text());
+        };
+        return text;
+    }
 
+    /// Scores every champion I played against the draft; names are corrected to the closest known ones
+    pub fn get_draft_scores(&self, allies: Vec<&str>, enemies: Vec<&str>) -> Vec<DraftScore> {
         let allies = self.guess_champion_names(allies);
         let enemies = self.guess_champion_names(enemies);
-        let allies_pointers: Vec<&str> = allies.iter().map(|s| s.as_str()).collect();
-        let enemies_pointers: Vec<&str> = enemies.iter().map(|s| s.as_str()).collect();
+        return self.score_draft(&allies, &enemies);
+    }
 
+    fn score_draft(&self, allies: &[String], enemies: &[String]) -> Vec<DraftScore> {
+        let champions = self.get_sorted_champions();
+        let mut draft_scores = Vec::new();
         for (champion_name, champion_info) in &champions {
-            let (matched_ally_count, ally_score, ally_breakdown_text) = Self::get_win_chance_summary(
-                champion_info.get_win_rates_with_champions(), &allies_pointers, 2
+            let ally_scores = self.get_matchup_scores(
+                champion_info.get_win_rates_with_champions(), champion_info.get_win_rates_with_classes(), allies
             );
-            let (matched_enemy_count, enemy_score, enemy_breakdown_text) = Self::get_win_chance_summary(
-                champion_info.get_win_rates_vs_champions(), &enemies_pointers, 2
+            let enemy_scores = self.get_matchup_scores(
+                champion_info.get_win_rates_vs_champions(), champion_info.get_win_rates_vs_classes(), enemies
             );
-            text.push_str(
-                format!("{}: ally strength {}, enemy weakness {}, summary chance {}",
-                    champion_name,
-                    Self::format_chance(matched_ally_count, ally_score),
-                    Self::format_chance(matched_enemy_count, enemy_score),
-                    Self::format_chance(
-                        matched_ally_count + matched_enemy_count,
-                        ally_score + enemy_score
-                    )
-                ).as_str()
-            );
-            text.push('\n');
-            text.push_str(INDENTATION_STRING);
-            text.push_str("Allies:\n");
-            text.push_str(ally_breakdown_text.as_str());
-            text.push_str(INDENTATION_STRING);
-            text.push_str("Enemies:\n");
-            text.push_str(enemy_breakdown_text.as_str());
+            draft_scores.push(DraftScore::new(champion_name, champion_info.count_of_matches, ally_scores, enemy_scores));
         };
-        return text;
+        return draft_scores;
     }
 
-    fn get_win_chance_summary(champion_infos: &HashMap<String, WinRateInfo>, champions: &Vec<&str>,
-            indentation_level: i32) -> (i32, f32, String) {
-        let mut matched_count: i32 = 0;
-        let mut combined_score: f32 = 0.0;
-        let mut breakdown_text = String::new();
-        for info in champion_infos {
-            if champions.contains(&info.0.as_str()) {
-                matched_count += 1;
-                combined_score += info.1.get_win_rate();
-                for _ in 0..indentation_level {
-                    breakdown_text.push_str(INDENTATION_STRING);
-                }
-                breakdown_text.push_str(info.0);
-                breakdown_text.push_str(" ");
-                breakdown_text.push_str(info.1.to_string().as_str());
-                breakdown_text.push('\n');
+    /// Best summary chance first, leaving out champions which are banned or picked by someone else.
+    /// Taken champions are compared by their exact names ignoring case, so that "leesin" leaves out "LeeSin"
+    /// while an unknown champion leaves out nothing; only the scoring corrects names to the closest known ones
+    pub fn get_recommended_picks(&self, allies: Vec<&str>, enemies: Vec<&str>, bans: Vec<&str>, limit: usize)
+            -> Vec<DraftScore> {
+        let taken_champions: Vec<&str> = bans.iter().chain(&allies).chain(&enemies).copied().collect();
+        let allies = self.guess_champion_names(allies);
+        let enemies = self.guess_champion_names(enemies);
+        let mut draft_scores = self.score_draft(&allies, &enemies);
+        draft_scores.retain(|draft_score| !taken_champions.iter()
+            .any(|taken_champion| taken_champion.eq_ignore_ascii_case(&draft_score.champion)));
+        draft_scores.sort_by(|a, b| b.summary_chance.unwrap_or(0.0).partial_cmp(&a.summary_chance.unwrap_or(0.0)).unwrap()
+            .then_with(|| b.count_of_matches.cmp(&a.count_of_matches)));
+        draft_scores.truncate(limit);
+        return draft_scores;
+    }
+
+    fn get_matchup_scores(&self, champion_infos: &HashMap<String, WinRateInfo>,
+            class_infos: &HashMap<String, WinRateInfo>, champions: &[String]) -> Vec<MatchupScore> {
+        let mut scores = Vec::new();
+        for champion_name in champions {
+            let info = champion_infos.get(champion_name);
+            let class_prior = self.get_class_prior(class_infos, champion_name);
+            if let Some(score) = MatchupScore::new(champion_name, info, class_prior) {
+                scores.push(score);
             }
         }
-        return (matched_count, combined_score, breakdown_text);
-    }
-
-    fn format_chance(champion_count: i32, combined_score: f32) -> String {
-        if champion_count == 0 {
-            return String::from("[?]")
-        } else {
-            let mut text = String::new();
-            text = text.add(&format_percent(combined_score / (champion_count as f32)));
-            text = text.add(" of ");
-            text = text.add(&champion_count.to_string());
-            return text;
+        return scores;
+    }
+
+    fn get_class_prior(&self, class_infos: &HashMap<String, WinRateInfo>, champion_name: &str) -> Option<f32> {
+        let chances: Vec<f32> = self.champion_classes.get_classes(champion_name).iter()
+            .filter_map(|class_name| class_infos.get(class_name))
+            .map(|info| info.get_win_chance())
+            .collect();
+        if chances.is_empty() {
+            return None;
         }
+        return Some(chances.iter().sum::<f32>() / (chances.len() as f32));
     }
 
     fn get_all_champion_names(&self) -> Vec<String> {
         let mut name_set: HashSet<String> = HashSet::new();
         for (champion_name, info) in &self.champion_infos {
             name_set.insert(champion_name.clone());
-            for (champion_name, _) in info.get_win_rates_vs_champions() {
+            for champion_name in info.get_win_rates_vs_champions().keys() {
                 name_set.insert(champion_name.clone());
             }
-            for (champion_name, _) in info.get_win_rates_with_champions() {
+            for champion_name in info.get_win_rates_with_champions().keys() {
                 name_set.insert(champion_name.clone());
             }
         }
@@ -210,10 +406,10 @@ impl Analyzer {
         let mut corrected_names: Vec<String> = Vec::new();
         let champion_names = self.get_all_champion_names();
         for name in &names {
-            let mut best_distance = std::usize::MAX;
+            let mut best_distance = usize::MAX;
             let mut best_match: Option<&String> = None;
             for actual_name in &champion_names {
-                let distance = edit_distance(actual_name, *name);
+                let distance = edit_distance(actual_name, name);
                 if distance < best_distance {
                     best_match = Some(actual_name);
                     best_distance = distance;
@@ -223,7 +419,7 @@ impl Analyzer {
                 Some(best_match) => {
                     corrected_names.push(best_match.clone());
                     if best_match != name {
-                        println!("Corrected champion name {} -> {}", name, best_match);
+                        info!(name, corrected_name = best_match, "Corrected champion name");
                     }
                 },
                 None => corrected_names.push(String::from(*name))
@@ -233,45 +429,200 @@ impl Analyzer {
     }
 }
 
-fn find_participants_by_team_id(info: &riven::models::match_v5::Info, team_id: riven::consts::Team, equal: bool)
-        -> Vec<&riven::models::match_v5::Participant> {
-    let mut matched_participants = Vec::new();
-    for participant in &info.participants {
-        let is_matched = equal && team_id == participant.team_id ||
-            !equal && team_id != participant.team_id;
-        if is_matched {
-            matched_participants.push(participant);
+/// Counts of one pass over the match files
+pub struct MatchFilesSummary {
+    pub total_files: usize,
+    /// Files within the duration limit
+    pub processed_files: usize,
+    /// Files which were not cached yet
+    pub parsed_files: usize,
+    /// Corrupt match files, which were left out
+    pub skipped_files: Vec<Error>,
+}
+
+/// Hands the facts of every match file to `add`, newest first, until the matches are older than `duration_limit`
+/// before the latest one.
+/// Match files are parsed in parallel batches, small enough that a short duration limit does not parse much more
+/// than it needs. Facts of parsed match files are cached in the data directory, so only new files are parsed again.
+pub fn read_match_facts(data_directory: &str, duration_limit: chrono::Duration, show_progress: bool,
+        progress_message: &'static str, mut add: impl FnMut(&MatchFacts, NaiveDateTime, PathBuf))
+        -> Result<MatchFilesSummary> {
+    let files = read_match_file_paths(data_directory)?;
+    let mut match_cache = MatchCache::load(data_directory);
+    let progress_bar = create_progress_bar(files.len(), progress_message, show_progress);
+    let mut summary = MatchFilesSummary {
+        total_files: files.len(),
+        processed_files: 0,
+        parsed_files: 0,
+        skipped_files: Vec::new(),
+    };
+    let mut latest_chronological_date: Option<NaiveDateTime> = None;
+    let mut latest_processed_date: Option<NaiveDateTime> = None;
+    'files: for batch in files.chunks(PARSE_BATCH_SIZE) {
+        let batch_facts: Vec<(u128, bool, Result<MatchFacts>)> = batch.par_iter()
+            .map(|file_path| {
+                let modified = get_modified(file_path);
+                let file_name = file_path.file_name().to_string_lossy().into_owned();
+                let cached_facts = match_cache.get(&file_name, modified).cloned();
+                let is_cached = cached_facts.is_some();
+                let facts = match cached_facts {
+                    Some(facts) => Ok(facts),
+                    None => load_match(&file_path.path()).map(|match_history| MatchFacts::new(&match_history)),
+                };
+                progress_bar.inc(1);
+                return (modified, is_cached, facts);
+            })
+            .collect();
+        for (file_path, (modified, is_cached, facts)) in batch.iter().zip(batch_facts) {
+            if let (Some(latest_chronological_date), Some(latest_processed_date)) =
+                (latest_chronological_date, latest_processed_date) {
+                let duration = latest_chronological_date
+                    .signed_duration_since(latest_processed_date);
+                if duration > duration_limit {
+                    debug!(%latest_processed_date, "Duration limit reached");
+                    break 'files;
+                }
+            }
+            let facts = match facts {
+                Ok(facts) => facts,
+                Err(error) => {
+                    progress_bar.suspend(|| warn!(%error, "Skipping file"));
+                    summary.skipped_files.push(error);
+                    continue;
+                }
+            };
+            let moment = facts.get_moment();
+            if latest_chronological_date.is_none() {
+                latest_chronological_date = Some(moment);
+            }
+            latest_processed_date = Some(moment);
+            debug!(file = %file_path.path().display(), %moment, is_cached, "Analyzing match");
+            add(&facts, moment, file_path.path());
+            summary.processed_files += 1;
+            if !is_cached {
+                summary.parsed_files += 1;
+                match_cache.insert(file_path.file_name().to_string_lossy().into_owned(), modified, facts);
+            }
         }
     }
-    return matched_participants;
+    progress_bar.finish_and_clear();
+    if let Err(error) = match_cache.save() {
+        warn!(%error, "Cannot save cache");
+    }
+    return Ok(summary);
 }
 
-#[derive(clap::Parser)]
-struct CommandLineArguments {
-    #[clap(short, default_value_t = 300)]
-    days: i64,
+/// Reads only the fields of `match_model`, not the full `riven` match
+pub fn load_match(file_path: &std::path::Path) -> Result<Match> {
+    let file_path_text = file_path.display().to_string();
+    let file_content = std::fs::read_to_string(file_path)
+        .map_err(|source| Error::from_io(&file_path_text, source))?;
+    return serde_json::from_str(&file_content)
+        .map_err(|source| Error::InvalidMatchJson { file_path: file_path_text, source });
+}
 
-    #[clap(long, default_value_t = String::from(""))]
-    allies: String,
+/// Stored match files, newest first; other files of the data directory such as the cache are left out
+pub fn read_match_file_paths(data_directory: &str) -> Result<Vec<std::fs::DirEntry>> {
+    let mut files: Vec<std::fs::DirEntry> = std::fs::read_dir(data_directory)
+        .and_then(|entries| entries.collect())
+        .map_err(|source| Error::from_io(data_directory, source))?;
+    files.retain(|file_path| file_path.path().extension().is_some_and(|extension| extension == "json"));
+    files.sort_by(|a, b| a.file_name().cmp(&b.file_name()).reverse());
+    return Ok(files);
+}
 
-    #[clap(long, default_value_t = String::from(""))]
-    enemies: String,
+fn get_team_classes<'a>(champion_classes: &ChampionClasses, champion_names: impl Iterator<Item = &'a str>)
+        -> HashSet<String> {
+    let mut classes = HashSet::new();
+    for champion_name in champion_names {
+        for class_name in champion_classes.get_classes(champion_name) {
+            classes.insert(class_name.clone());
+        }
+    }
+    return classes;
 }
 
-pub fn analyze() {
-    let summoner_id_file_path = String::from("./summoner-id.txt");
-    let summoner_id = std::fs::read_to_string(summoner_id_file_path.as_str())
-        .expect(format!("File {} is required", summoner_id_file_path).as_str());
-    let args = CommandLineArguments::parse_from(std::env::args().skip(1));
-    let mut analyzer = Analyzer::new(summoner_id);
-    analyzer.duration_limit = chrono::Duration::days(args.days);
-    analyzer.analyze_files().unwrap();
-
-    if args.allies.len() > 0 || args.enemies.len() > 0 {
-        let allies: Vec<&str> = args.allies.split(',').filter(|s| s.len() > 0).collect();
-        let enemies: Vec<&str> = args.enemies.split(',').filter(|s| s.len() > 0).collect();
-        println!("Champion chances:\n{}", analyzer.get_score_summary_text(allies, enemies));
-    } else {
-        println!("Champion summary:\n{}", analyzer.get_summary_text());
-    }
-}
\ No newline at end of file
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tilt::TiltInfo;
+
+    fn create_participant(summoner_id: &str, champion_name: &str, team_id: u16, win: bool) -> ParticipantFacts {
+        return ParticipantFacts {
+            summoner_id: String::from(summoner_id),
+            puuid: String::new(),
+            name: String::from(summoner_id),
+            summoner_name: String::from(summoner_id),
+            champion_name: String::from(champion_name),
+            role: String::new(),
+            team_id,
+            win,
+            physical_damage: 0,
+            magic_damage: 0,
+        };
+    }
+
+    fn create_analyzer(my_champions: &[&str]) -> Analyzer {
+        let mut analyzer = Analyzer::new(String::new(), String::from("me"), ChampionClasses::new());
+        for (index, champion_name) in my_champions.iter().enumerate() {
+            let facts = MatchFacts {
+                game_creation: index as i64 * 3_600_000,
+                game_duration: 1800,
+                abnormality: None,
+                participants: vec![
+                    create_participant("me", champion_name, 100, true),
+                    create_participant("enemy", "Garen", 200, false),
+                ],
+            };
+            analyzer.add_match_facts(&facts, facts.get_moment(), PathBuf::new());
+        }
+        return analyzer;
+    }
+
+    /// A 35 minute match from before patch 11.20, which gives the duration in milliseconds
+    fn create_old_match_facts(game_creation: i64) -> MatchFacts {
+        let match_history: Match = serde_json::from_value(serde_json::json!({
+            "metadata": { "matchId": "EUW1_1" },
+            "info": {
+                "gameCreation": game_creation,
+                "gameDuration": 2_100_000,
+                "participants": [
+                    { "summonerId": "me", "championName": "Ahri", "teamId": 100, "win": true },
+                    { "summonerId": "enemy", "championName": "Garen", "teamId": 200, "win": false },
+                ],
+            },
+        })).unwrap();
+        return MatchFacts::new(&match_history);
+    }
+
+    #[test]
+    fn old_matches_count_by_game_length_and_session() {
+        let mut analyzer = Analyzer::new(String::new(), String::from("me"), ChampionClasses::new());
+        // Newest first, three hours apart
+        for game_creation in [1_650_010_800_000_i64, 1_650_000_000_000] {
+            let facts = create_old_match_facts(game_creation);
+            analyzer.add_match_facts(&facts, facts.get_moment(), PathBuf::new());
+        }
+        assert!(analyzer.get_match_results().iter().all(|result| result.game_duration == 2100));
+        let game_lengths = analyzer.get_champion_infos()["Ahri"].get_win_rates_by_game_length();
+        assert_eq!(game_lengths.keys().collect::<Vec<_>>(), vec!["30-40 min"]);
+        assert_eq!(TiltInfo::new(analyzer.get_match_results(), 1).count_of_sessions, 2);
+    }
+
+    #[test]
+    fn recommended_picks_leave_out_taken_champions_of_any_case() {
+        let analyzer = create_analyzer(&["LeeSin", "Ahri", "Zed", "Lux"]);
+        let picks = analyzer.get_recommended_picks(vec!["ahri"], vec!["zed"], vec!["leesin"], 5);
+        let champions: Vec<&str> = picks.iter().map(|pick| pick.champion.as_str()).collect();
+        assert_eq!(champions, vec!["Lux"]);
+    }
+
+    #[test]
+    fn recommended_picks_keep_champions_when_an_unknown_champion_is_taken() {
+        let analyzer = create_analyzer(&["Lux", "Ahri", "Zed"]);
+        let picks = analyzer.get_recommended_picks(vec![], vec![], vec!["Vex"], 5);
+        let mut champions: Vec<&str> = picks.iter().map(|pick| pick.champion.as_str()).collect();
+        champions.sort();
+        assert_eq!(champions, vec!["Ahri", "Lux", "Zed"]);
+    }
+}
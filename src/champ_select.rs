@@ -0,0 +1,184 @@
+//! Draft of the champion select in the running League client, read from its local API (LCU).
+//!
+//! The client writes its port and password to a lockfile like `LeagueClient:1234:54321:password:https`.
+//! Requests use basic authentication as `riot` and the client's self-signed certificate is accepted.
+
+use serde::Deserialize;
+use crate::error::{Error, Result};
+use crate::live::find_champion_name;
+
+const SESSION_PATH: &str = "/lol-champ-select/v1/session";
+const USER_NAME: &str = "riot";
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PlayerSelection {
+    cell_id: i64,
+    #[serde(default)]
+    champion_id: i64,
+    /// The champion hovered before locking in
+    #[serde(default)]
+    champion_pick_intent: i64,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+struct Bans {
+    #[serde(default)]
+    my_team_bans: Vec<i64>,
+    #[serde(default)]
+    their_team_bans: Vec<i64>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Session {
+    local_player_cell_id: i64,
+    #[serde(default)]
+    my_team: Vec<PlayerSelection>,
+    #[serde(default)]
+    their_team: Vec<PlayerSelection>,
+    #[serde(default)]
+    bans: Bans,
+}
+
+/// Picks and bans so far; champions not picked yet are left out
+#[derive(PartialEq)]
+pub struct ChampSelectDraft {
+    /// Locked in or hovered
+    pub my_champion: Option<String>,
+    pub allies: Vec<String>,
+    pub enemies: Vec<String>,
+    pub bans: Vec<String>,
+    /// Picked or banned champions newer than the `riven` champion table, left out of the lists above
+    pub unknown_champion_ids: Vec<i64>,
+}
+
+pub struct LcuConnection {
+    base_url: String,
+    password: String,
+    client: reqwest::Client,
+}
+
+impl LcuConnection {
+    pub fn new(base_url: String, password: String) -> Result<LcuConnection> {
+        let client = reqwest::Client::builder()
+            .danger_accept_invalid_certs(true)
+            .build()
+            .map_err(|error| Error::Request { action: String::from("connect to the League client"), message: error.to_string() })?;
+        return Ok(LcuConnection {
+            base_url: String::from(base_url.trim_end_matches('/')),
+            password,
+            client,
+        });
+    }
+
+    /// Connects to the client which wrote the lockfile, on 127.0.0.1
+    pub fn from_lockfile(file_path: &str) -> Result<LcuConnection> {
+        let content = std::fs::read_to_string(file_path)
+            .map_err(|source| Error::from_io(file_path, source))?;
+        let fields: Vec<&str> = content.trim().split(':').collect();
+        if fields.len() < 5 {
+            return Err(Error::Request {
+                action: String::from("connect to the League client"),
+                message: format!("unexpected lockfile content in {}", file_path),
+            });
+        }
+        return LcuConnection::new(format!("{}://127.0.0.1:{}", fields[4], fields[2]), String::from(fields[3]));
+    }
+
+    /// `None` when the client is not in champion select
+    pub async fn read_draft(&self) -> Result<Option<ChampSelectDraft>> {
+        let request_error = |error: reqwest::Error| Error::Request {
+            action: String::from("read champion select"),
+            message: error.to_string(),
+        };
+        let response = self.client.get(format!("{}{}", self.base_url, SESSION_PATH))
+            .basic_auth(USER_NAME, Some(&self.password))
+            .send().await
+            .map_err(request_error)?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        let session: Session = response.error_for_status()
+            .map_err(request_error)?
+            .json().await
+            .map_err(request_error)?;
+        return Ok(Some(ChampSelectDraft::new(&session)));
+    }
+}
+
+impl ChampSelectDraft {
+    fn new(session: &Session) -> ChampSelectDraft {
+        let mut draft = ChampSelectDraft {
+            my_champion: None,
+            allies: Vec::new(),
+            enemies: Vec::new(),
+            bans: Vec::new(),
+            unknown_champion_ids: Vec::new(),
+        };
+        for selection in &session.my_team {
+            let champion_id = if selection.champion_id > 0 { selection.champion_id } else { selection.champion_pick_intent };
+            if champion_id <= 0 {
+                continue;
+            }
+            if selection.cell_id == session.local_player_cell_id {
+                draft.my_champion = draft.get_champion_name(champion_id);
+            } else if selection.champion_id > 0 {
+                // Teammates count with their selected champion, not with their intent
+                if let Some(champion_name) = draft.get_champion_name(champion_id) {
+                    draft.allies.push(champion_name);
+                }
+            }
+        }
+        for selection in &session.their_team {
+            if selection.champion_id > 0 {
+                if let Some(champion_name) = draft.get_champion_name(selection.champion_id) {
+                    draft.enemies.push(champion_name);
+                }
+            }
+        }
+        for champion_id in session.bans.my_team_bans.iter().chain(&session.bans.their_team_bans) {
+            if *champion_id > 0 {
+                if let Some(champion_name) = draft.get_champion_name(*champion_id) {
+                    draft.bans.push(champion_name);
+                }
+            }
+        }
+        return draft;
+    }
+
+    /// Unknown champions are remembered instead of being corrected to the name of some other champion
+    fn get_champion_name(&mut self, champion_id: i64) -> Option<String> {
+        let champion_name = find_champion_name(champion_id);
+        if champion_name.is_none() {
+            self.unknown_champion_ids.push(champion_id);
+        }
+        return champion_name;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unknown_champions_are_left_out() {
+        // 103 is Ahri, 910 is Hwei which the champion table does not know
+        let session: Session = serde_json::from_value(serde_json::json!({
+            "localPlayerCellId": 0,
+            "myTeam": [
+                { "cellId": 0, "championId": 0, "championPickIntent": 910 },
+                { "cellId": 1, "championId": 103 },
+            ],
+            "theirTeam": [ { "cellId": 5, "championId": 910 } ],
+            "bans": { "myTeamBans": [910], "theirTeamBans": [103] },
+        })).unwrap();
+        let draft = ChampSelectDraft::new(&session);
+        assert_eq!(draft.my_champion, None);
+        assert_eq!(draft.allies, vec!["Ahri"]);
+        assert!(draft.enemies.is_empty());
+        assert_eq!(draft.bans, vec!["Ahri"]);
+        assert_eq!(draft.unknown_champion_ids, vec![910, 910, 910]);
+    }
+}
@@ -0,0 +1,80 @@
+use std::collections::HashMap;
+use serde_json::Value;
+use crate::error::{Error, Result};
+
+/// Maps champion names (as they appear in `Participant::champion_name`) to their classes.
+///
+/// Accepts either a user-edited mapping `{ "Ahri": ["Mage", "Assassin"] }`
+/// or the Data Dragon `champion.json` file, in which case the `tags` of every champion are used.
+/// Data Dragon has no tag for engage, so a user-edited mapping can add an "Engage" class for champions
+/// that start fights, e.g. `{ "Leona": ["Tank", "Support", "Engage"] }`.
+pub struct ChampionClasses {
+    classes: HashMap<String, Vec<String>>,
+}
+
+impl ChampionClasses {
+    pub fn new() -> ChampionClasses {
+        return ChampionClasses {
+            classes: HashMap::new(),
+        }
+    }
+
+    /// Missing file means no class information: class statistics are simply left empty.
+    pub fn load(file_path: &str) -> Result<ChampionClasses> {
+        let mut champion_classes = ChampionClasses::new();
+        if !std::path::Path::new(file_path).exists() {
+            return Ok(champion_classes);
+        }
+        let file_content = std::fs::read_to_string(file_path)
+            .map_err(|source| Error::from_io(file_path, source))?;
+        let root: Value = serde_json::from_str(&file_content)
+            .map_err(|source| Error::InvalidChampionClasses { file_path: String::from(file_path), source })?;
+        let data_dragon_champions = root.get("data").and_then(|data| data.as_object());
+        match data_dragon_champions {
+            Some(champions) => {
+                for (champion_id, champion) in champions {
+                    champion_classes.read_class_list(champion_id, champion.get("tags"));
+                }
+            },
+            None => {
+                if let Some(champions) = root.as_object() {
+                    for (champion_name, classes) in champions {
+                        champion_classes.read_class_list(champion_name, Some(classes));
+                    }
+                }
+            }
+        }
+        return Ok(champion_classes);
+    }
+
+    fn read_class_list(&mut self, champion_name: &str, classes: Option<&Value>) {
+        let classes: Vec<String> = classes
+            .and_then(|classes| classes.as_array())
+            .map(|classes| classes.iter()
+                .filter_map(|class| class.as_str())
+                .map(String::from)
+                .collect())
+            .unwrap_or_default();
+        if !classes.is_empty() {
+            self.classes.insert(String::from(champion_name), classes);
+        }
+    }
+
+    /// Whether any champion has the class, so that a class missing from the file is not counted as 0
+    pub fn has_class(&self, class_name: &str) -> bool {
+        return self.classes.values().any(|classes| classes.iter().any(|name| name == class_name));
+    }
+
+    pub fn get_classes(&self, champion_name: &str) -> &[String] {
+        return match self.classes.get(champion_name) {
+            Some(classes) => classes.as_slice(),
+            None => &[],
+        };
+    }
+}
+
+impl Default for ChampionClasses {
+    fn default() -> ChampionClasses {
+        return ChampionClasses::new();
+    }
+}
@@ -1,28 +1,65 @@
 use std::ops::Add;
 use std::collections::HashMap;
 use crate::string::*;
-use crate::win_rate_info::WinRateInfo;
+use crate::win_rate_info::{WinRateInfo, DEFAULT_SIGNIFICANCE_THRESHOLD};
 
-const SUMMARY_LIMIT: usize = 6;
+/// Length of every best and worst list in the summary, unless set otherwise
+pub const DEFAULT_SUMMARY_LIMIT: usize = 6;
+
+const BLUE_TEAM_ID: u16 = 100;
+pub const SIDE_NAMES: [&str; 2] = ["blue side", "red side"];
+/// Upper bounds in minutes of every game length but the last
+const GAME_LENGTH_LIMITS: [i64; 3] = [20, 30, 40];
+pub const GAME_LENGTH_NAMES: [&str; 4] = ["under 20 min", "20-30 min", "30-40 min", "40+ min"];
+
+pub fn get_side_name(team_id: u16) -> &'static str {
+    return if team_id == BLUE_TEAM_ID { SIDE_NAMES[0] } else { SIDE_NAMES[1] };
+}
+
+/// The game duration is in seconds, as `MatchFacts` keeps it for match files of every patch
+pub fn get_game_length_name(game_duration: i64) -> &'static str {
+    let minutes = game_duration / 60;
+    let index = GAME_LENGTH_LIMITS.iter()
+        .position(|limit| minutes < *limit)
+        .unwrap_or(GAME_LENGTH_LIMITS.len());
+    return GAME_LENGTH_NAMES[index];
+}
 
 pub struct ChampionInfo {
     pub count_of_matches: i32,
     win_rates_vs_champions: HashMap<String, WinRateInfo>,
     win_rates_with_champions: HashMap<String, WinRateInfo>,
+    win_rates_vs_classes: HashMap<String, WinRateInfo>,
+    win_rates_with_classes: HashMap<String, WinRateInfo>,
+    /// Keyed by `SIDE_NAMES`
+    win_rates_by_side: HashMap<String, WinRateInfo>,
+    /// Keyed by `GAME_LENGTH_NAMES`
+    win_rates_by_game_length: HashMap<String, WinRateInfo>,
+    /// Given to every win rate of this champion
+    significance_threshold: i32,
 }
 
 impl ChampionInfo {
     pub fn new() -> ChampionInfo {
+        return ChampionInfo::with_significance_threshold(DEFAULT_SIGNIFICANCE_THRESHOLD);
+    }
+
+    pub fn with_significance_threshold(significance_threshold: i32) -> ChampionInfo {
         return ChampionInfo {
             count_of_matches: 0,
             win_rates_vs_champions: HashMap::new(),
             win_rates_with_champions: HashMap::new(),
+            win_rates_vs_classes: HashMap::new(),
+            win_rates_with_classes: HashMap::new(),
+            win_rates_by_side: HashMap::new(),
+            win_rates_by_game_length: HashMap::new(),
+            significance_threshold,
         }
     }
 
     pub fn get_win_rate_vs(&mut self, champion_name: &String) -> &mut WinRateInfo {
         if !self.win_rates_vs_champions.contains_key(champion_name) {
-            let info = WinRateInfo::new();
+            let info = WinRateInfo::with_significance_threshold(self.significance_threshold);
             self.win_rates_vs_champions.insert(champion_name.clone(), info);
         }
         return self.win_rates_vs_champions.get_mut(champion_name).unwrap();
@@ -30,16 +67,44 @@ impl ChampionInfo {
 
     pub fn get_win_rate_with(&mut self, champion_name: &String) -> &mut WinRateInfo {
         if !self.win_rates_with_champions.contains_key(champion_name) {
-            let info = WinRateInfo::new();
+            let info = WinRateInfo::with_significance_threshold(self.significance_threshold);
             self.win_rates_with_champions.insert(champion_name.clone(), info);
         }
         return self.win_rates_with_champions.get_mut(champion_name).unwrap();
     }
 
-    fn get_significant_list(source: &HashMap<String, WinRateInfo>) -> Vec<(&str, &WinRateInfo)> {
+    pub fn get_win_rate_vs_class(&mut self, class_name: &String) -> &mut WinRateInfo {
+        if !self.win_rates_vs_classes.contains_key(class_name) {
+            let info = WinRateInfo::with_significance_threshold(self.significance_threshold);
+            self.win_rates_vs_classes.insert(class_name.clone(), info);
+        }
+        return self.win_rates_vs_classes.get_mut(class_name).unwrap();
+    }
+
+    pub fn get_win_rate_with_class(&mut self, class_name: &String) -> &mut WinRateInfo {
+        if !self.win_rates_with_classes.contains_key(class_name) {
+            let info = WinRateInfo::with_significance_threshold(self.significance_threshold);
+            self.win_rates_with_classes.insert(class_name.clone(), info);
+        }
+        return self.win_rates_with_classes.get_mut(class_name).unwrap();
+    }
+
+    pub fn get_win_rate_on_side(&mut self, side_name: &str) -> &mut WinRateInfo {
+        let significance_threshold = self.significance_threshold;
+        return self.win_rates_by_side.entry(String::from(side_name))
+            .or_insert_with(|| WinRateInfo::with_significance_threshold(significance_threshold));
+    }
+
+    pub fn get_win_rate_by_game_length(&mut self, game_length_name: &str) -> &mut WinRateInfo {
+        let significance_threshold = self.significance_threshold;
+        return self.win_rates_by_game_length.entry(String::from(game_length_name))
+            .or_insert_with(|| WinRateInfo::with_significance_threshold(significance_threshold));
+    }
+
+    pub(crate) fn get_significant_list(source: &HashMap<String, WinRateInfo>) -> Vec<(&str, &WinRateInfo)> {
         let mut significant_champions: Vec<(&str, &WinRateInfo)> = Vec::new();
         for (champion_name, win_rate_info) in source {
-            significant_champions.push((&champion_name, &win_rate_info));
+            significant_champions.push((champion_name, win_rate_info));
         }
         significant_champions.sort_by(|a, b|
             a.1.get_win_chance().partial_cmp(&b.1.get_win_chance()).unwrap()
@@ -47,14 +112,15 @@ impl ChampionInfo {
         return significant_champions;
     }
 
-    fn format_top_summary_list(title: &str, sorted_champions: &Vec<(&str, &WinRateInfo)>, reverse: bool) -> String {
+    pub(crate) fn format_top_summary_list(title: &str, sorted_champions: &Vec<(&str, &WinRateInfo)>, reverse: bool,
+            limit: usize) -> String {
         let mut relevant_champions: Vec<(&str, &WinRateInfo)> = Vec::new();
         if reverse {
-            for champion in sorted_champions.iter().rev().take(SUMMARY_LIMIT) {
+            for champion in sorted_champions.iter().rev().take(limit) {
                 relevant_champions.push(*champion);
             }
         } else {
-            for champion in sorted_champions.iter().take(SUMMARY_LIMIT) {
+            for champion in sorted_champions.iter().take(limit) {
                 relevant_champions.push(*champion);
             }
         }
@@ -66,7 +132,19 @@ impl ChampionInfo {
         return text;
     }
 
-    pub fn get_summary_text(&self) -> String {
+    /// Keeps the order of `names` and leaves out names without matches
+    fn format_ordered_summary_list(title: &str, source: &HashMap<String, WinRateInfo>, names: &[&str]) -> String {
+        let list: Vec<(&str, &WinRateInfo)> = names.iter()
+            .filter_map(|name| source.get(*name).map(|win_rate_info| (*name, win_rate_info)))
+            .collect();
+        let mut text = String::from(title);
+        text.push_str(":\n");
+        text = text.add(&WinRateInfo::format_list_of_named(&list, INDENTATION_STRING));
+        return text;
+    }
+
+    /// `limit` is the length of every best and worst list
+    pub fn get_summary_text(&self, limit: usize) -> String {
         let mut text = String::new();
         text = text
             .add("count of matches: ")
@@ -75,14 +153,27 @@ impl ChampionInfo {
 
         {
             let allies = ChampionInfo::get_significant_list(&self.win_rates_with_champions);
-            text = text.add(&ChampionInfo::format_top_summary_list("best allies", &allies, true));
-            text = text.add(&ChampionInfo::format_top_summary_list("worst allies", &allies, false));
+            text = text.add(&ChampionInfo::format_top_summary_list("best allies", &allies, true, limit));
+            text = text.add(&ChampionInfo::format_top_summary_list("worst allies", &allies, false, limit));
         }
         {
             let enemies = ChampionInfo::get_significant_list(&self.win_rates_vs_champions);
-            text = text.add(&ChampionInfo::format_top_summary_list("easiest enemies", &enemies, true));
-            text = text.add(&ChampionInfo::format_top_summary_list("worst enemies", &enemies, false));
+            text = text.add(&ChampionInfo::format_top_summary_list("easiest enemies", &enemies, true, limit));
+            text = text.add(&ChampionInfo::format_top_summary_list("worst enemies", &enemies, false, limit));
+        }
+        if !self.win_rates_with_classes.is_empty() {
+            let classes = ChampionInfo::get_significant_list(&self.win_rates_with_classes);
+            text = text.add(&ChampionInfo::format_top_summary_list("best ally classes", &classes, true, limit));
+            text = text.add(&ChampionInfo::format_top_summary_list("worst ally classes", &classes, false, limit));
         }
+        if !self.win_rates_vs_classes.is_empty() {
+            let classes = ChampionInfo::get_significant_list(&self.win_rates_vs_classes);
+            text = text.add(&ChampionInfo::format_top_summary_list("easiest enemy classes", &classes, true, limit));
+            text = text.add(&ChampionInfo::format_top_summary_list("worst enemy classes", &classes, false, limit));
+        }
+        text = text.add(&ChampionInfo::format_ordered_summary_list("by side", &self.win_rates_by_side, &SIDE_NAMES));
+        text = text.add(&ChampionInfo::format_ordered_summary_list(
+            "by game length", &self.win_rates_by_game_length, &GAME_LENGTH_NAMES));
         return text;
     }
 
@@ -93,4 +184,26 @@ impl ChampionInfo {
     pub fn get_win_rates_with_champions(&self) -> &HashMap<String, WinRateInfo> {
         return &self.win_rates_with_champions;
     }
+
+    pub fn get_win_rates_vs_classes(&self) -> &HashMap<String, WinRateInfo> {
+        return &self.win_rates_vs_classes;
+    }
+
+    pub fn get_win_rates_with_classes(&self) -> &HashMap<String, WinRateInfo> {
+        return &self.win_rates_with_classes;
+    }
+
+    pub fn get_win_rates_by_side(&self) -> &HashMap<String, WinRateInfo> {
+        return &self.win_rates_by_side;
+    }
+
+    pub fn get_win_rates_by_game_length(&self) -> &HashMap<String, WinRateInfo> {
+        return &self.win_rates_by_game_length;
+    }
+}
+
+impl Default for ChampionInfo {
+    fn default() -> ChampionInfo {
+        return ChampionInfo::new();
+    }
 }
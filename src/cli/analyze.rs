@@ -0,0 +1,81 @@
+use league_think::analyze::Analyzer;
+use league_think::champion_class::ChampionClasses;
+use league_think::error::Result;
+use league_think::json_output::AnalysisJson;
+use crate::cli::config::Settings;
+
+#[derive(clap::ArgEnum, Clone, PartialEq)]
+enum OutputFormat {
+    Text,
+    /// See `json_output` for the schema
+    Json,
+}
+
+#[derive(clap::Args)]
+pub struct CommandLineArguments {
+    /// Comma separated ally champions to score a draft
+    #[clap(long, default_value_t = String::from(""))]
+    allies: String,
+
+    /// Comma separated enemy champions to score a draft
+    #[clap(long, default_value_t = String::from(""))]
+    enemies: String,
+
+    /// Show win rates by team composition instead of the champion summary
+    #[clap(long)]
+    composition: bool,
+
+    /// Show my record with recurring teammates and against recurring opponents instead of the champion summary
+    #[clap(long, conflicts_with = "composition")]
+    players: bool,
+
+    /// Output format; JSON holds the champions, composition and players at once
+    #[clap(long, arg_enum, default_value = "text")]
+    format: OutputFormat,
+}
+
+/// Analyzes the stored matches of the last `days` days of the settings
+pub fn create_analyzer(settings: &Settings) -> Result<Analyzer> {
+    let champion_classes = ChampionClasses::load(&settings.champion_classes_file)?;
+    let mut analyzer = Analyzer::new(settings.data_dir.clone(), settings.get_summoner_id()?, champion_classes);
+    analyzer.set_duration_limit(chrono::Duration::days(settings.days));
+    analyzer.set_show_progress(!settings.quiet);
+    analyzer.set_saturation_threshold(settings.statistical_saturation_threshold);
+    analyzer.set_significance_threshold(settings.statistical_significance_threshold);
+    analyzer.set_summary_limit(settings.summary_limit);
+    analyzer.set_exclude_early_surrenders(settings.exclude_early_surrenders);
+    analyzer.analyze_files()?;
+    return Ok(analyzer);
+}
+
+pub fn analyze(settings: &Settings, args: CommandLineArguments) -> Result<()> {
+    let analyzer = create_analyzer(settings)?;
+
+    let draft_scores = if !args.allies.is_empty() || !args.enemies.is_empty() {
+        let allies: Vec<&str> = args.allies.split(',').filter(|s| !s.is_empty()).collect();
+        let enemies: Vec<&str> = args.enemies.split(',').filter(|s| !s.is_empty()).collect();
+        Some(analyzer.get_draft_scores(allies, enemies))
+    } else {
+        None
+    };
+
+    if args.format == OutputFormat::Json {
+        let champions = analyzer.get_sorted_champions();
+        let json = AnalysisJson::new(&champions, analyzer.get_composition_info(), analyzer.get_filtered_matches(),
+            analyzer.get_player_infos(), draft_scores.as_deref());
+        println!("{}", serde_json::to_string_pretty(&json).expect("Serialize analysis"));
+        return Ok(());
+    }
+    // Every text output tells how many matches it leaves out
+    print!("{}", analyzer.get_filtered_summary_text());
+    if let Some(draft_scores) = draft_scores {
+        println!("Champion chances:\n{}", analyzer.get_score_summary_text(&draft_scores));
+    } else if args.composition {
+        println!("Team composition:\n{}", analyzer.get_composition_info().get_summary_text());
+    } else if args.players {
+        println!("Players:\n{}", analyzer.get_player_summary_text());
+    } else {
+        println!("Champion summary:\n{}", analyzer.get_summary_text());
+    }
+    return Ok(());
+}
@@ -0,0 +1,105 @@
+use std::time::Duration;
+use league_think::analyze::Analyzer;
+use league_think::champ_select::{ChampSelectDraft, LcuConnection};
+use league_think::error::Result;
+use crate::cli::analyze::create_analyzer;
+use crate::cli::config::Settings;
+use crate::cli::create_runtime;
+use tracing::{info, warn};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+#[cfg(target_os = "windows")]
+const DEFAULT_LOCKFILE_PATH: &str = "C:\\Riot Games\\League of Legends\\lockfile";
+#[cfg(target_os = "macos")]
+const DEFAULT_LOCKFILE_PATH: &str = "/Applications/League of Legends.app/Contents/LoL/lockfile";
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+const DEFAULT_LOCKFILE_PATH: &str = "./lockfile";
+
+#[derive(clap::Args)]
+pub struct CommandLineArguments {
+    /// Lockfile of the running League client
+    #[clap(long, default_value_t = String::from(DEFAULT_LOCKFILE_PATH))]
+    lockfile: String,
+
+    /// Base URL of the client API instead of the lockfile, e.g. of a fake local endpoint
+    #[clap(long, requires = "lcu-password")]
+    lcu_url: Option<String>,
+
+    /// Password of the client API given by --lcu-url
+    #[clap(long)]
+    lcu_password: Option<String>,
+
+    /// Count of recommended picks
+    #[clap(long, default_value_t = 5)]
+    limit: usize,
+
+    /// Print the current draft once instead of following the champion select
+    #[clap(long)]
+    once: bool,
+}
+
+fn print_draft(analyzer: &Analyzer, draft: &ChampSelectDraft, limit: usize) {
+    if let Some(my_champion) = &draft.my_champion {
+        println!("Selected: {}", my_champion);
+    }
+    println!("Allies: {}", draft.allies.join(", "));
+    println!("Enemies: {}", draft.enemies.join(", "));
+    println!("Bans: {}", draft.bans.join(", "));
+    if !draft.unknown_champion_ids.is_empty() {
+        warn!(champion_ids = ?draft.unknown_champion_ids, "Leaving out unknown champions");
+    }
+    let recommended_picks = analyzer.get_recommended_picks(
+        draft.allies.iter().map(String::as_str).collect(),
+        draft.enemies.iter().map(String::as_str).collect(),
+        draft.bans.iter().map(String::as_str).collect(),
+        limit);
+    println!("Recommended picks:\n{}", analyzer.get_score_summary_text(&recommended_picks));
+}
+
+pub fn champ_select(settings: &Settings, args: CommandLineArguments) -> Result<()> {
+    let analyzer = create_analyzer(settings)?;
+    print!("{}", analyzer.get_filtered_summary_text());
+    let connection = match (args.lcu_url, args.lcu_password) {
+        (Some(lcu_url), Some(lcu_password)) => LcuConnection::new(lcu_url, lcu_password)?,
+        _ => LcuConnection::from_lockfile(&args.lockfile)?,
+    };
+
+    let runtime = create_runtime()?;
+    return runtime.block_on(async {
+        let mut previous_draft: Option<ChampSelectDraft> = None;
+        let mut is_waiting = false;
+        // Only the end of the champion select or Ctrl-C stops following it, the client may restart meanwhile
+        loop {
+            let draft = match connection.read_draft().await {
+                Ok(draft) => draft,
+                Err(error) if !args.once => {
+                    warn!(%error, "Cannot read champion select, retrying");
+                    tokio::time::sleep(POLL_INTERVAL).await;
+                    continue;
+                },
+                Err(error) => return Err(error),
+            };
+            match draft {
+                Some(draft) if previous_draft.as_ref() != Some(&draft) => {
+                    print_draft(&analyzer, &draft, args.limit);
+                    previous_draft = Some(draft);
+                },
+                Some(_) => {},
+                None if previous_draft.is_some() => {
+                    info!("Champion select ended");
+                    return Ok(());
+                },
+                None if !is_waiting => {
+                    info!("Waiting for champion select");
+                    is_waiting = true;
+                },
+                None => {},
+            }
+            if args.once {
+                return Ok(());
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    });
+}
@@ -0,0 +1,385 @@
+//! Settings resolved from the configuration file, the command line and environment variables.
+//!
+//! Every value is looked up in this order, the first one found wins:
+//! environment variable, command line option, selected profile, top level of the configuration file, default.
+//!
+//! ```toml
+//! profile = "main"          # profile used when --profile is not given
+//! days = 300
+//! exclude_early_surrenders = false   # remakes and AFK games are always left out
+//!
+//! [thresholds]
+//! statistical_saturation = 12
+//! statistical_significance = 5
+//! summary_limit = 6
+//!
+//! [profiles.main]
+//! summoner_name = "YumaWhen"
+//! summoner_id_file = "./summoner-id.txt"
+//! platform = "EUW1"
+//! data_dir = "./data"
+//!
+//! [profiles.smurf]
+//! summoner_name = "Smurf"
+//! summoner_id = "..."
+//! data_dir = "./data-smurf"
+//! ```
+
+use std::collections::HashMap;
+use std::str::FromStr;
+use riven::consts::PlatformRoute;
+use tracing::debug;
+use league_think::champion_info::DEFAULT_SUMMARY_LIMIT;
+use league_think::error::{Error, Result};
+use crate::cli::options::GlobalOptions;
+use league_think::win_rate_info::DEFAULT_SIGNIFICANCE_THRESHOLD;
+
+pub const DEFAULT_CONFIG_FILE_PATH: &str = "./league-think.toml";
+const DEFAULT_DAYS: i64 = 300;
+const DEFAULT_STATISTICAL_SATURATION_THRESHOLD: i32 = 12;
+
+pub const RIOT_API_KEY_VARIABLE: &str = "RIOT_API_KEY";
+pub const PROFILE_VARIABLE: &str = "LEAGUE_THINK_PROFILE";
+pub const DATA_DIR_VARIABLE: &str = "LEAGUE_THINK_DATA_DIR";
+pub const SUMMONER_ID_VARIABLE: &str = "LEAGUE_THINK_SUMMONER_ID";
+pub const SUMMONER_NAME_VARIABLE: &str = "LEAGUE_THINK_SUMMONER_NAME";
+pub const PLATFORM_VARIABLE: &str = "LEAGUE_THINK_PLATFORM";
+
+/// Values which can be set both at the top level of the file and per profile
+#[derive(serde::Deserialize, Default, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct ProfileConfig {
+    pub data_dir: Option<String>,
+    pub summoner_id: Option<String>,
+    pub summoner_id_file: Option<String>,
+    pub summoner_name: Option<String>,
+    pub api_key_file: Option<String>,
+    pub champion_classes_file: Option<String>,
+    pub platform: Option<String>,
+    pub days: Option<i64>,
+    pub exclude_early_surrenders: Option<bool>,
+    pub thresholds: Option<ThresholdConfig>,
+}
+
+#[derive(serde::Deserialize, Default, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct ThresholdConfig {
+    pub statistical_saturation: Option<i32>,
+    pub statistical_significance: Option<i32>,
+    pub summary_limit: Option<usize>,
+}
+
+#[derive(Default)]
+pub struct ConfigFile {
+    /// Profile used when none is selected on the command line
+    pub profile: Option<String>,
+    pub profiles: HashMap<String, ProfileConfig>,
+    /// Values at the top level of the file
+    pub defaults: ProfileConfig,
+}
+
+impl ConfigFile {
+    pub fn load(file_path: &str) -> Result<ConfigFile> {
+        let file_content = std::fs::read_to_string(file_path)
+            .map_err(|source| Error::from_io(file_path, source))?;
+        let invalid_config = |error: toml::de::Error|
+            Error::InvalidConfig { file_path: String::from(file_path), message: error.to_string() };
+        // The profile keys are split off by hand, so that unknown top level values are rejected like in a profile
+        let mut table: toml::Table = toml::from_str(&file_content).map_err(invalid_config)?;
+        let profile = table.remove("profile")
+            .map(toml::Value::try_into).transpose().map_err(invalid_config)?;
+        let profiles = table.remove("profiles")
+            .map(toml::Value::try_into).transpose().map_err(invalid_config)?
+            .unwrap_or_default();
+        let defaults = toml::Value::Table(table).try_into().map_err(invalid_config)?;
+        return Ok(ConfigFile { profile, profiles, defaults });
+    }
+
+    /// Profile values on top of the top level values
+    fn get_profile(&self, name: Option<&str>) -> Result<ProfileConfig> {
+        let defaults = self.defaults.clone();
+        let profile = match name {
+            Some(name) => self.profiles.get(name).cloned()
+                .ok_or_else(|| Error::UnknownProfile(String::from(name)))?,
+            None => return Ok(defaults),
+        };
+        let default_thresholds = defaults.thresholds.unwrap_or_default();
+        let thresholds = profile.thresholds.unwrap_or_default();
+        return Ok(ProfileConfig {
+            data_dir: profile.data_dir.or(defaults.data_dir),
+            summoner_id: profile.summoner_id.or(defaults.summoner_id),
+            summoner_id_file: profile.summoner_id_file.or(defaults.summoner_id_file),
+            summoner_name: profile.summoner_name.or(defaults.summoner_name),
+            api_key_file: profile.api_key_file.or(defaults.api_key_file),
+            champion_classes_file: profile.champion_classes_file.or(defaults.champion_classes_file),
+            platform: profile.platform.or(defaults.platform),
+            days: profile.days.or(defaults.days),
+            exclude_early_surrenders: profile.exclude_early_surrenders.or(defaults.exclude_early_surrenders),
+            thresholds: Some(ThresholdConfig {
+                statistical_saturation: thresholds.statistical_saturation
+                    .or(default_thresholds.statistical_saturation),
+                statistical_significance: thresholds.statistical_significance
+                    .or(default_thresholds.statistical_significance),
+                summary_limit: thresholds.summary_limit.or(default_thresholds.summary_limit),
+            }),
+        });
+    }
+}
+
+pub struct Settings {
+    pub data_dir: String,
+    pub quiet: bool,
+    summoner_id: Option<String>,
+    summoner_id_file: String,
+    pub summoner_name: String,
+    api_key: Option<String>,
+    api_key_file: String,
+    pub champion_classes_file: String,
+    pub platform: PlatformRoute,
+    pub days: i64,
+    pub exclude_early_surrenders: bool,
+    pub statistical_saturation_threshold: i32,
+    pub statistical_significance_threshold: i32,
+    pub summary_limit: usize,
+}
+
+fn read_variable(name: &str) -> Option<String> {
+    return std::env::var(name).ok().filter(|value| !value.is_empty());
+}
+
+impl Settings {
+    pub fn load(options: &GlobalOptions) -> Result<Settings> {
+        let config_file = match &options.config {
+            Some(file_path) => ConfigFile::load(file_path)?,
+            None if std::path::Path::new(DEFAULT_CONFIG_FILE_PATH).exists() => ConfigFile::load(DEFAULT_CONFIG_FILE_PATH)?,
+            None => ConfigFile::default(),
+        };
+        let profile_name = read_variable(PROFILE_VARIABLE)
+            .or_else(|| options.profile.clone())
+            .or_else(|| config_file.profile.clone());
+        let profile = config_file.get_profile(profile_name.as_deref())?;
+        let thresholds = profile.thresholds.unwrap_or_default();
+        let platform = read_variable(PLATFORM_VARIABLE)
+            .or_else(|| options.platform.clone())
+            .or(profile.platform)
+            .unwrap_or_else(|| String::from("EUW1"));
+        return Ok(Settings {
+            data_dir: read_variable(DATA_DIR_VARIABLE)
+                .or_else(|| options.data_dir.clone())
+                .or(profile.data_dir)
+                .unwrap_or_else(|| String::from("./data")),
+            quiet: options.quiet,
+            summoner_id: read_variable(SUMMONER_ID_VARIABLE)
+                .or(profile.summoner_id),
+            summoner_id_file: options.summoner_id_file.clone()
+                .or(profile.summoner_id_file)
+                .unwrap_or_else(|| String::from("./summoner-id.txt")),
+            summoner_name: read_variable(SUMMONER_NAME_VARIABLE)
+                .or_else(|| options.summoner_name.clone())
+                .or(profile.summoner_name)
+                .unwrap_or_else(|| String::from("YumaWhen")),
+            api_key: read_variable(RIOT_API_KEY_VARIABLE),
+            api_key_file: options.api_key_file.clone()
+                .or(profile.api_key_file)
+                .unwrap_or_else(|| String::from("./riot-api-key.txt")),
+            champion_classes_file: options.champion_classes_file.clone()
+                .or(profile.champion_classes_file)
+                .unwrap_or_else(|| String::from("./champion-classes.json")),
+            platform: PlatformRoute::from_str(&platform)
+                .map_err(|_| Error::UnknownPlatform(platform.clone()))?,
+            days: options.days.or(profile.days).unwrap_or(DEFAULT_DAYS),
+            exclude_early_surrenders: match (options.exclude_early_surrenders, options.keep_early_surrenders) {
+                (true, _) => true,
+                (_, true) => false,
+                _ => profile.exclude_early_surrenders.unwrap_or(false),
+            },
+            statistical_saturation_threshold: thresholds.statistical_saturation
+                .unwrap_or(DEFAULT_STATISTICAL_SATURATION_THRESHOLD),
+            statistical_significance_threshold: thresholds.statistical_significance
+                .unwrap_or(DEFAULT_SIGNIFICANCE_THRESHOLD),
+            summary_limit: thresholds.summary_limit.unwrap_or(DEFAULT_SUMMARY_LIMIT),
+        });
+    }
+
+    /// The summoner id set directly takes precedence over the summoner id file
+    pub fn get_summoner_id(&self) -> Result<String> {
+        return match &self.summoner_id {
+            Some(summoner_id) => Ok(summoner_id.clone()),
+            None => std::fs::read_to_string(&self.summoner_id_file)
+                .map_err(|source| Error::from_io(&self.summoner_id_file, source)),
+        };
+    }
+
+    /// The `RIOT_API_KEY` environment variable takes precedence over the key file
+    pub fn get_api_key(&self) -> Result<String> {
+        return match &self.api_key {
+            Some(api_key) => Ok(api_key.clone()),
+            None => std::fs::read_to_string(&self.api_key_file)
+                .map_err(|source| Error::from_io(&self.api_key_file, source)),
+        };
+    }
+
+    /// Logged at debug level, shown with --verbose
+    pub fn log(&self) {
+        debug!(data_dir = %self.data_dir, "Data directory");
+        match &self.summoner_id {
+            Some(_) => debug!("Summoner id set directly"),
+            None => debug!(file = %self.summoner_id_file, "Summoner id file"),
+        }
+        debug!(summoner_name = %self.summoner_name, platform = %self.platform, "Summoner");
+        match &self.api_key {
+            Some(_) => debug!(variable = RIOT_API_KEY_VARIABLE, "Riot API key from environment"),
+            None => debug!(file = %self.api_key_file, "Riot API key file"),
+        }
+        debug!(file = %self.champion_classes_file, "Champion classes file");
+        debug!(exclude_early_surrenders = self.exclude_early_surrenders, "Match filter");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    /// Environment variables are shared by the tests running in parallel
+    static ENVIRONMENT_LOCK: Mutex<()> = Mutex::new(());
+
+    const CONFIG: &str = r#"
+profile = "main"
+data_dir = "./top"
+summoner_name = "TopName"
+platform = "KR"
+days = 100
+
+[thresholds]
+statistical_saturation = 20
+summary_limit = 3
+
+[profiles.main]
+data_dir = "./main"
+summoner_name = "MainName"
+days = 50
+
+[profiles.main.thresholds]
+summary_limit = 8
+
+[profiles.smurf]
+summoner_name = "Smurf"
+"#;
+
+    /// Written to the temporary directory under a name unique to the test
+    fn write_config(name: &str, content: &str) -> String {
+        let file_path = std::env::temp_dir()
+            .join(format!("league_think_config_{}_{}.toml", std::process::id(), name));
+        std::fs::write(&file_path, content).unwrap();
+        return file_path.display().to_string();
+    }
+
+    fn load_settings(config: &str, options: GlobalOptions, variables: &[(&str, &str)]) -> Result<Settings> {
+        let _lock = ENVIRONMENT_LOCK.lock().unwrap_or_else(|error| error.into_inner());
+        for (name, value) in variables {
+            std::env::set_var(name, value);
+        }
+        let options = GlobalOptions { config: Some(String::from(config)), ..options };
+        let settings = Settings::load(&options);
+        for (name, _) in variables {
+            std::env::remove_var(name);
+        }
+        return settings;
+    }
+
+    #[test]
+    fn profile_values_override_top_level_values() {
+        let config_file = ConfigFile::load(&write_config("profile", CONFIG)).unwrap();
+        assert_eq!(config_file.profile.as_deref(), Some("main"));
+        let profile = config_file.get_profile(Some("main")).unwrap();
+        assert_eq!(profile.data_dir.as_deref(), Some("./main"));
+        assert_eq!(profile.platform.as_deref(), Some("KR"));
+        assert_eq!(profile.days, Some(50));
+        let thresholds = profile.thresholds.unwrap();
+        assert_eq!(thresholds.statistical_saturation, Some(20));
+        assert_eq!(thresholds.statistical_significance, None);
+        assert_eq!(thresholds.summary_limit, Some(8));
+        let top_level = config_file.get_profile(None).unwrap();
+        assert_eq!(top_level.data_dir.as_deref(), Some("./top"));
+        assert!(matches!(config_file.get_profile(Some("missing")), Err(Error::UnknownProfile(_))));
+    }
+
+    #[test]
+    fn unknown_keys_are_rejected() {
+        let top_level = write_config("unknown_top_level", "data_dir = \"./data\"\nsumoner_name = \"Typo\"\n");
+        assert!(matches!(ConfigFile::load(&top_level), Err(Error::InvalidConfig { .. })));
+        let profile = write_config("unknown_profile", "[profiles.main]\nsumoner_name = \"Typo\"\n");
+        assert!(matches!(ConfigFile::load(&profile), Err(Error::InvalidConfig { .. })));
+        let thresholds = write_config("unknown_thresholds", "[thresholds]\nsaturation = 3\n");
+        assert!(matches!(ConfigFile::load(&thresholds), Err(Error::InvalidConfig { .. })));
+    }
+
+    #[test]
+    fn settings_lookup_order() {
+        let config = write_config("lookup_order", CONFIG);
+        let settings = load_settings(&config, GlobalOptions::default(), &[]).unwrap();
+        assert_eq!(settings.data_dir, "./main");
+        assert_eq!(settings.summoner_name, "MainName");
+        assert_eq!(settings.platform, PlatformRoute::KR);
+        assert_eq!(settings.days, 50);
+        assert_eq!(settings.statistical_saturation_threshold, 20);
+        assert_eq!(settings.statistical_significance_threshold, DEFAULT_SIGNIFICANCE_THRESHOLD);
+        assert_eq!(settings.summary_limit, 8);
+
+        let options = GlobalOptions {
+            data_dir: Some(String::from("./option")),
+            summoner_name: Some(String::from("OptionName")),
+            platform: Some(String::from("NA1")),
+            days: Some(10),
+            ..GlobalOptions::default()
+        };
+        let settings = load_settings(&config, options, &[]).unwrap();
+        assert_eq!(settings.data_dir, "./option");
+        assert_eq!(settings.summoner_name, "OptionName");
+        assert_eq!(settings.platform, PlatformRoute::NA1);
+        assert_eq!(settings.days, 10);
+
+        let options = GlobalOptions {
+            data_dir: Some(String::from("./option")),
+            summoner_name: Some(String::from("OptionName")),
+            platform: Some(String::from("NA1")),
+            ..GlobalOptions::default()
+        };
+        let variables = [(DATA_DIR_VARIABLE, "./variable"), (SUMMONER_NAME_VARIABLE, "VariableName"),
+            (PLATFORM_VARIABLE, "BR1")];
+        let settings = load_settings(&config, options, &variables).unwrap();
+        assert_eq!(settings.data_dir, "./variable");
+        assert_eq!(settings.summoner_name, "VariableName");
+        assert_eq!(settings.platform, PlatformRoute::BR1);
+    }
+
+    #[test]
+    fn settings_profile_selection() {
+        let config = write_config("profile_selection", CONFIG);
+        let options = GlobalOptions { profile: Some(String::from("smurf")), ..GlobalOptions::default() };
+        let settings = load_settings(&config, options, &[]).unwrap();
+        assert_eq!(settings.summoner_name, "Smurf");
+        // Values the profile lacks come from the top level
+        assert_eq!(settings.data_dir, "./top");
+        assert_eq!(settings.days, 100);
+        assert_eq!(settings.summary_limit, 3);
+
+        let options = GlobalOptions { profile: Some(String::from("smurf")), ..GlobalOptions::default() };
+        let settings = load_settings(&config, options, &[(PROFILE_VARIABLE, "main")]).unwrap();
+        assert_eq!(settings.summoner_name, "MainName");
+    }
+
+    #[test]
+    fn settings_defaults() {
+        let config = write_config("defaults", "");
+        let settings = load_settings(&config, GlobalOptions::default(), &[]).unwrap();
+        assert_eq!(settings.data_dir, "./data");
+        assert_eq!(settings.summoner_name, "YumaWhen");
+        assert_eq!(settings.platform, PlatformRoute::EUW1);
+        assert_eq!(settings.days, DEFAULT_DAYS);
+        assert_eq!(settings.statistical_saturation_threshold, DEFAULT_STATISTICAL_SATURATION_THRESHOLD);
+        assert_eq!(settings.summary_limit, DEFAULT_SUMMARY_LIMIT);
+        assert!(!settings.exclude_early_surrenders);
+    }
+}
@@ -0,0 +1,25 @@
+use league_think::duo::DuoAnalyzer;
+use league_think::error::Result;
+use crate::cli::config::Settings;
+
+#[derive(clap::Args)]
+pub struct CommandLineArguments {
+    /// Summoner id, puuid, Riot ID or summoner name of the first player
+    first_player: String,
+
+    /// Summoner id, puuid, Riot ID or summoner name of the second player
+    second_player: String,
+
+}
+
+pub fn duo(settings: &Settings, args: CommandLineArguments) -> Result<()> {
+    let mut analyzer = DuoAnalyzer::new(settings.data_dir.clone(), args.first_player, args.second_player);
+    analyzer.set_duration_limit(chrono::Duration::days(settings.days));
+    analyzer.set_show_progress(!settings.quiet);
+    analyzer.set_exclude_early_surrenders(settings.exclude_early_surrenders);
+    analyzer.set_significance_threshold(settings.statistical_significance_threshold);
+    analyzer.set_summary_limit(settings.summary_limit);
+    analyzer.analyze_files()?;
+    println!("Duo:\n{}", analyzer.get_summary_text());
+    return Ok(());
+}
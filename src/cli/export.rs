@@ -0,0 +1,103 @@
+use std::collections::HashMap;
+use std::path::Path;
+use crate::cli::config::Settings;
+use league_think::analyze::{load_match, Analyzer};
+use crate::cli::analyze::create_analyzer;
+use league_think::champion_info::ChampionInfo;
+use league_think::error::{Error, Result};
+use league_think::match_facts::get_moment_of_timestamp;
+use league_think::string::format_csv_row;
+use league_think::win_rate_info::WinRateInfo;
+use tracing::info;
+
+const WIN_RATE_HEADER: [&str; 6] = ["champion", "other_champion", "wins", "matches", "win_rate", "win_chance"];
+const PARTICIPANT_HEADER: [&str; 19] = [
+    "match_id", "game_creation", "game_duration", "queue_id", "team_id", "win",
+    "puuid", "display_name", "champion_name", "team_position",
+    "kills", "deaths", "assists", "gold_earned", "total_minions_killed",
+    "physical_damage_dealt_to_champions", "magic_damage_dealt_to_champions", "true_damage_dealt_to_champions",
+    "time_played",
+];
+
+#[derive(clap::Args)]
+pub struct CommandLineArguments {
+    /// Directory for allies.csv, enemies.csv and participants.csv
+    #[clap(long, default_value_t = String::from("./export"))]
+    output: String,
+}
+
+/// One row per pair of my champion and an ally or enemy champion, ready for a pivot table
+fn format_win_rate_table(analyzer: &Analyzer,
+        get_win_rates: fn(&ChampionInfo) -> &HashMap<String, WinRateInfo>) -> String {
+    let mut text = format_csv_row(&WIN_RATE_HEADER.map(String::from));
+    for (champion_name, champion_info) in analyzer.get_sorted_champions() {
+        let mut win_rates: Vec<(&String, &WinRateInfo)> = get_win_rates(champion_info).iter().collect();
+        win_rates.sort_by(|a, b| a.0.cmp(b.0));
+        for (other_champion_name, win_rate_info) in win_rates {
+            text.push_str(&format_csv_row(&[
+                champion_name.clone(),
+                other_champion_name.clone(),
+                win_rate_info.get_count_of_wins().to_string(),
+                win_rate_info.get_count_of_matches().to_string(),
+                win_rate_info.get_win_rate().to_string(),
+                win_rate_info.get_win_chance().to_string(),
+            ]));
+        }
+    }
+    return text;
+}
+
+/// Every participant of the matches the analysis counted, so all files cover the same matches
+fn format_participant_table(analyzer: &Analyzer) -> Result<String> {
+    let mut text = format_csv_row(&PARTICIPANT_HEADER.map(String::from));
+    for match_result in analyzer.get_match_results() {
+        let match_history = load_match(&match_result.file_path)?;
+        let info = &match_history.info;
+        let moment = get_moment_of_timestamp(info.game_creation);
+        for participant in &info.participants {
+            text.push_str(&format_csv_row(&[
+                match_history.metadata.match_id.clone(),
+                moment.to_string(),
+                match_result.game_duration.to_string(),
+                info.queue_id.to_string(),
+                participant.team_id.to_string(),
+                participant.win.to_string(),
+                participant.puuid.clone(),
+                participant.get_display_name(),
+                participant.champion_name.clone(),
+                participant.team_position.clone(),
+                participant.kills.to_string(),
+                participant.deaths.to_string(),
+                participant.assists.to_string(),
+                participant.gold_earned.to_string(),
+                participant.total_minions_killed.to_string(),
+                participant.physical_damage_dealt_to_champions.to_string(),
+                participant.magic_damage_dealt_to_champions.to_string(),
+                participant.true_damage_dealt_to_champions.to_string(),
+                participant.time_played.to_string(),
+            ]));
+        }
+    }
+    return Ok(text);
+}
+
+fn write_file(file_path: &Path, text: String) -> Result<()> {
+    return std::fs::write(file_path, text)
+        .map_err(|source| Error::Io { file_path: file_path.display().to_string(), source });
+}
+
+pub fn export(settings: &Settings, args: CommandLineArguments) -> Result<()> {
+    let analyzer = create_analyzer(settings)?;
+    let output = Path::new(&args.output);
+    std::fs::create_dir_all(output)
+        .map_err(|source| Error::Io { file_path: args.output.clone(), source })?;
+
+    let allies_text = format_win_rate_table(&analyzer, ChampionInfo::get_win_rates_with_champions);
+    write_file(&output.join("allies.csv"), allies_text)?;
+    let enemies_text = format_win_rate_table(&analyzer, ChampionInfo::get_win_rates_vs_champions);
+    write_file(&output.join("enemies.csv"), enemies_text)?;
+    let participants_text = format_participant_table(&analyzer)?;
+    write_file(&output.join("participants.csv"), participants_text)?;
+    info!(output = %output.display(), "Exported");
+    return Ok(());
+}
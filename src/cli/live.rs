@@ -0,0 +1,50 @@
+use league_think::error::{Error, Result};
+use league_think::live::{get_default_base_url, read_live_draft};
+use crate::cli::analyze::create_analyzer;
+use crate::cli::config::Settings;
+use crate::cli::create_runtime;
+
+#[derive(clap::Args)]
+pub struct CommandLineArguments {
+    /// My puuid [default: taken from the stored matches]
+    #[clap(long)]
+    puuid: Option<String>,
+
+    /// Base URL of the Riot API, e.g. of a local mock server [default: https://{platform}.api.riotgames.com]
+    #[clap(long)]
+    api_url: Option<String>,
+}
+
+pub fn live(settings: &Settings, args: CommandLineArguments) -> Result<()> {
+    let analyzer = create_analyzer(settings)?;
+    print!("{}", analyzer.get_filtered_summary_text());
+    let puuid = match args.puuid.as_deref().or_else(|| analyzer.get_puuid()) {
+        Some(puuid) => String::from(puuid),
+        None => return Err(Error::Request {
+            action: String::from("read live game"),
+            message: String::from("my puuid is not in the stored matches, set it with --puuid"),
+        }),
+    };
+    let api_key = settings.get_api_key()?;
+    let base_url = args.api_url.unwrap_or_else(|| get_default_base_url(settings.platform));
+
+    let runtime = create_runtime()?;
+    let draft = runtime.block_on(read_live_draft(&api_key, &base_url, &puuid))?;
+    let draft = match draft {
+        Some(draft) => draft,
+        None => {
+            println!("Not in a game");
+            return Ok(());
+        }
+    };
+    if let Some(my_champion) = &draft.my_champion {
+        println!("Playing: {}", my_champion);
+    }
+    println!("Allies: {}", draft.allies.join(", "));
+    println!("Enemies: {}", draft.enemies.join(", "));
+    let draft_scores = analyzer.get_draft_scores(
+        draft.allies.iter().map(String::as_str).collect(),
+        draft.enemies.iter().map(String::as_str).collect());
+    println!("Champion chances:\n{}", analyzer.get_score_summary_text(&draft_scores));
+    return Ok(());
+}
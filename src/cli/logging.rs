@@ -0,0 +1,28 @@
+//! Leveled logs on stderr, so that stdout only carries results.
+//!
+//! The level is `info`, `debug` with --verbose and `warn` with --quiet.
+//! The `LEAGUE_THINK_LOG` environment variable takes precedence, e.g. `LEAGUE_THINK_LOG=league_think=trace`.
+
+use std::io::IsTerminal;
+use tracing_subscriber::EnvFilter;
+
+const LOG_VARIABLE: &str = "LEAGUE_THINK_LOG";
+
+pub fn init(verbose: bool, quiet: bool) {
+    let level = if verbose {
+        "debug"
+    } else if quiet {
+        "warn"
+    } else {
+        "info"
+    };
+    let filter = EnvFilter::try_from_env(LOG_VARIABLE)
+        .unwrap_or_else(|_| EnvFilter::new(level));
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(std::io::stderr)
+        .with_ansi(std::io::stderr().is_terminal())
+        .with_target(false)
+        .without_time()
+        .init();
+}
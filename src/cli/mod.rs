@@ -0,0 +1,24 @@
+//! Commands of the `league_think` executable
+pub mod analyze;
+pub mod champ_select;
+pub mod config;
+pub mod duo;
+pub mod export;
+pub mod live;
+pub mod logging;
+pub mod options;
+pub mod pool;
+pub mod rank;
+pub mod report;
+pub mod serve;
+pub mod store;
+pub mod tilt;
+pub mod tui;
+
+use league_think::error::{Error, Result};
+
+/// Runtime for the commands talking to the Riot API or the League client
+pub fn create_runtime() -> Result<tokio::runtime::Runtime> {
+    return tokio::runtime::Runtime::new()
+        .map_err(|source| Error::Runtime { action: String::from("start the async runtime"), source });
+}
@@ -0,0 +1,59 @@
+/// Options shared by all commands; unset ones come from the configuration file
+#[derive(clap::Args, Default)]
+pub struct GlobalOptions {
+    /// Directory with the stored match files [default: ./data]
+    #[clap(long, global = true)]
+    pub data_dir: Option<String>,
+
+    /// Configuration file [default: ./league-think.toml if it exists]
+    #[clap(long, global = true)]
+    pub config: Option<String>,
+
+    /// Profile of the configuration file
+    #[clap(long, global = true)]
+    pub profile: Option<String>,
+
+    /// Analyze matches of this many days before the latest one [default: 300]
+    #[clap(short, long, global = true)]
+    pub days: Option<i64>,
+
+    /// Log more details about what is going on
+    #[clap(short, long, global = true)]
+    pub verbose: bool,
+
+    /// Only log warnings and errors and hide progress bars, stdout keeps the results
+    #[clap(short, long, global = true, conflicts_with = "verbose")]
+    pub quiet: bool,
+
+    /// Disable colors even when the output is a terminal
+    #[clap(long, global = true)]
+    pub no_color: bool,
+
+    /// Summoner whose ranked matches are downloaded [default: YumaWhen]
+    #[clap(long, global = true)]
+    pub summoner_name: Option<String>,
+
+    /// Platform of the summoner such as EUW1 [default: EUW1]
+    #[clap(long, global = true)]
+    pub platform: Option<String>,
+
+    /// File with the summoner id of the player to analyze [default: ./summoner-id.txt]
+    #[clap(long, global = true)]
+    pub summoner_id_file: Option<String>,
+
+    /// File with the Riot API key, unless RIOT_API_KEY is set [default: ./riot-api-key.txt]
+    #[clap(long, global = true)]
+    pub api_key_file: Option<String>,
+
+    /// Leave out matches ended by an early surrender vote, remakes and AFK games are always left out
+    #[clap(long, global = true)]
+    pub exclude_early_surrenders: bool,
+
+    /// Count matches ended by an early surrender vote, even when the configuration leaves them out
+    #[clap(long, global = true, conflicts_with = "exclude-early-surrenders")]
+    pub keep_early_surrenders: bool,
+
+    /// Champion classes: a name to classes mapping or the Data Dragon champion.json [default: ./champion-classes.json]
+    #[clap(long, global = true)]
+    pub champion_classes_file: Option<String>,
+}
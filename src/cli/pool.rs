@@ -0,0 +1,14 @@
+use league_think::error::Result;
+use crate::cli::analyze::create_analyzer;
+use crate::cli::config::Settings;
+
+pub fn pool(settings: &Settings) -> Result<()> {
+    let analyzer = create_analyzer(settings)?;
+    print!("{}", analyzer.get_filtered_summary_text());
+    let mut text = String::new();
+    for recommendation in analyzer.get_pool_recommendations() {
+        text.push_str(&recommendation.get_summary_text());
+    }
+    println!("Champion pools:\n{}", text);
+    return Ok(());
+}
@@ -0,0 +1,13 @@
+use league_think::error::Result;
+use league_think::rank::{load_rank_history, RankInfo};
+use crate::cli::analyze::create_analyzer;
+use crate::cli::config::Settings;
+
+pub fn rank(settings: &Settings) -> Result<()> {
+    let history = load_rank_history(&settings.data_dir)?;
+    let analyzer = create_analyzer(settings)?;
+    print!("{}", analyzer.get_filtered_summary_text());
+    let rank_info = RankInfo::new(&history, analyzer.get_match_results(), settings.statistical_significance_threshold);
+    println!("Rank:\n{}", rank_info.get_summary_text());
+    return Ok(());
+}
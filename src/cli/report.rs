@@ -0,0 +1,158 @@
+use std::collections::HashMap;
+use std::path::Path;
+use crate::cli::config::Settings;
+use league_think::analyze::MatchResult;
+use crate::cli::analyze::create_analyzer;
+use league_think::error::{Error, Result};
+use league_think::string::{ escape_html, format_percent };
+use league_think::win_rate_info::WinRateInfo;
+use tracing::info;
+
+/// Count of the latest matches which make up one point of the overall win rate chart
+const WIN_RATE_WINDOW: usize = 20;
+const CHAMPION_WIN_RATE_WINDOW: usize = 10;
+const CHART_WIDTH: f32 = 640.0;
+const CHART_HEIGHT: f32 = 160.0;
+
+const STYLE: &str = "
+body { font-family: sans-serif; margin: 2em; background: #101418; color: #e0e0e0; }
+h2 { display: flex; align-items: center; gap: 0.5em; margin-top: 2em; }
+h2 img { width: 40px; height: 40px; border-radius: 4px; }
+.tables { display: flex; flex-wrap: wrap; gap: 2em; }
+table { border-collapse: collapse; }
+th { cursor: pointer; text-align: left; border-bottom: 1px solid #506070; padding: 2px 8px; }
+td { padding: 2px 8px; }
+td.good { color: #60d060; }
+td.bad { color: #e06060; }
+svg { background: #182028; }
+";
+
+const SCRIPT: &str = "
+document.querySelectorAll('table.sortable th').forEach(function (header) {
+    header.addEventListener('click', function () {
+        var column = header.cellIndex;
+        var table = header.closest('table');
+        var body = table.tBodies[0];
+        var ascending = header.dataset.ascending !== 'true';
+        header.dataset.ascending = ascending;
+        var rows = Array.from(body.rows);
+        rows.sort(function (a, b) {
+            var x = a.cells[column].dataset.value || a.cells[column].textContent;
+            var y = b.cells[column].dataset.value || b.cells[column].textContent;
+            var result = isNaN(x) || isNaN(y) ? x.localeCompare(y) : x - y;
+            return ascending ? result : -result;
+        });
+        rows.forEach(function (row) { body.appendChild(row); });
+    });
+});
+";
+
+#[derive(clap::Args)]
+pub struct CommandLineArguments {
+    #[clap(long, default_value_t = String::from("./report.html"))]
+    output: String,
+
+    /// Directory with Data Dragon champion icons such as Ahri.png
+    #[clap(long, default_value_t = String::from("./static/champion"))]
+    icons: String,
+}
+
+fn format_icon(icon_directory: &Path, champion_name: &str) -> String {
+    let file_path = icon_directory.join(String::from(champion_name) + ".png");
+    return match std::fs::read(file_path) {
+        Ok(image) => format!("<img src=\"data:image/png;base64,{}\" alt=\"\">", base64::encode(image)),
+        Err(_) => String::new(),
+    };
+}
+
+/// Rolling win rate over the latest `window` matches; `results` are oldest first
+fn format_win_rate_chart(results: &[&MatchResult], window: usize) -> String {
+    if results.len() < 2 {
+        return String::from("<p>Not enough matches for a chart</p>\n");
+    }
+    let first_moment = results[0].moment;
+    let total_duration = results[results.len() - 1].moment.signed_duration_since(first_moment).num_seconds();
+    let mut points = String::new();
+    for i in 0..results.len() {
+        let start = (i + 1).saturating_sub(window);
+        let recent_results = &results[start..=i];
+        let count_of_wins = recent_results.iter().filter(|result| result.win).count();
+        let win_rate = (count_of_wins as f32) / (recent_results.len() as f32);
+        let progress = if total_duration > 0 {
+            (results[i].moment.signed_duration_since(first_moment).num_seconds() as f32) / (total_duration as f32)
+        } else {
+            (i as f32) / ((results.len() - 1) as f32)
+        };
+        points.push_str(&format!("{:.1},{:.1} ", progress * CHART_WIDTH, (1.0 - win_rate) * CHART_HEIGHT));
+    }
+    let mut text = format!("<svg width=\"{}\" height=\"{}\" viewBox=\"0 -10 {} {}\">\n",
+        CHART_WIDTH, CHART_HEIGHT + 30.0, CHART_WIDTH, CHART_HEIGHT + 30.0);
+    text.push_str(&format!(
+        "<line x1=\"0\" y1=\"{0}\" x2=\"{1}\" y2=\"{0}\" stroke=\"#506070\" stroke-dasharray=\"4\"/>\n",
+        CHART_HEIGHT / 2.0, CHART_WIDTH));
+    text.push_str(&format!("<polyline points=\"{}\" fill=\"none\" stroke=\"#60a0e0\" stroke-width=\"2\"/>\n", points));
+    text.push_str(&format!("<text x=\"0\" y=\"{}\" fill=\"#a0a0a0\" font-size=\"12\">{}</text>\n",
+        CHART_HEIGHT + 15.0, first_moment.date()));
+    text.push_str(&format!(
+        "<text x=\"{}\" y=\"{}\" fill=\"#a0a0a0\" font-size=\"12\" text-anchor=\"end\">{}</text>\n",
+        CHART_WIDTH, CHART_HEIGHT + 15.0, results[results.len() - 1].moment.date()));
+    text.push_str(&format!("<text x=\"{}\" y=\"{}\" fill=\"#a0a0a0\" font-size=\"12\" text-anchor=\"end\">50%</text>\n",
+        CHART_WIDTH, CHART_HEIGHT / 2.0 - 4.0));
+    text.push_str("</svg>\n");
+    return text;
+}
+
+fn format_win_rate_table(title: &str, source: &HashMap<String, WinRateInfo>) -> String {
+    let mut win_rates: Vec<(&String, &WinRateInfo)> = source.iter().collect();
+    win_rates.sort_by(|a, b| b.1.get_win_chance().partial_cmp(&a.1.get_win_chance()).unwrap());
+    let mut text = format!(
+        "<table class=\"sortable\">\n<thead><tr><th>{}</th><th>chance</th><th>win rate</th><th>matches</th></tr></thead>\n<tbody>\n",
+        escape_html(title));
+    for (name, info) in win_rates {
+        let class_name = if info.get_win_chance() > 0.5 { "good" }
+            else if info.get_win_chance() < 0.5 { "bad" }
+            else { "" };
+        text.push_str(&format!(
+            "<tr><td>{}</td><td class=\"{}\" data-value=\"{}\">{}</td><td data-value=\"{}\">{}</td><td>{}</td></tr>\n",
+            escape_html(name),
+            class_name, info.get_win_chance(), format_percent(info.get_win_chance()),
+            info.get_win_rate(), format_percent(info.get_win_rate()),
+            info.get_count_of_matches()
+        ));
+    }
+    text.push_str("</tbody>\n</table>\n");
+    return text;
+}
+
+pub fn report(settings: &Settings, args: CommandLineArguments) -> Result<()> {
+    let analyzer = create_analyzer(settings)?;
+    let icon_directory = Path::new(&args.icons);
+    let results: Vec<&MatchResult> = analyzer.get_match_results().iter().rev().collect();
+
+    let mut html = String::from("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>League think report</title>\n");
+    html.push_str(&format!("<style>{}</style>\n</head>\n<body>\n", STYLE));
+    html.push_str(&format!("<h1>League think report</h1>\n<p>{} matches</p>\n", results.len()));
+    let filtered_summary = analyzer.get_filtered_summary_text();
+    if !filtered_summary.is_empty() {
+        html.push_str(&format!("<p>{}</p>\n", escape_html(filtered_summary.trim_end())));
+    }
+    html.push_str(&format_win_rate_chart(&results, WIN_RATE_WINDOW));
+    for (champion_name, champion_info) in analyzer.get_sorted_champions() {
+        html.push_str(&format!("<h2>{}{} <small>{} matches</small></h2>\n",
+            format_icon(icon_directory, champion_name), escape_html(champion_name), champion_info.count_of_matches));
+        let champion_results: Vec<&MatchResult> = results.iter()
+            .filter(|result| &result.champion_name == champion_name)
+            .copied()
+            .collect();
+        html.push_str(&format_win_rate_chart(&champion_results, CHAMPION_WIN_RATE_WINDOW));
+        html.push_str("<div class=\"tables\">\n");
+        html.push_str(&format_win_rate_table("ally", champion_info.get_win_rates_with_champions()));
+        html.push_str(&format_win_rate_table("enemy", champion_info.get_win_rates_vs_champions()));
+        html.push_str("</div>\n");
+    }
+    html.push_str(&format!("<script>{}</script>\n</body>\n</html>\n", SCRIPT));
+    std::fs::write(&args.output, html)
+        .map_err(|source| Error::Io { file_path: args.output.clone(), source })?;
+    info!(output = %args.output, "Report saved");
+    return Ok(());
+}
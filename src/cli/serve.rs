@@ -0,0 +1,148 @@
+use league_think::analyze::{read_match_file_paths, Analyzer};
+use league_think::error::{Error, Result};
+use league_think::json_output::{ChampionJson, ChampionListJson, DraftRequestJson, DraftResponseJson, ErrorJson};
+use crate::cli::analyze::create_analyzer;
+use crate::cli::config::Settings;
+use tracing::{debug, info, warn};
+
+const DEFAULT_PICK_LIMIT: usize = 5;
+
+#[derive(clap::Args)]
+pub struct CommandLineArguments {
+    /// Address to listen on; see `json_output` for the API
+    #[clap(long, default_value_t = String::from("127.0.0.1:8350"))]
+    address: String,
+}
+
+/// The analysis is redone when the newest match file or the count of match files changed
+struct ServedAnalyzer<'a> {
+    settings: &'a Settings,
+    analyzer: Analyzer,
+    match_files_signature: (usize, Option<String>),
+}
+
+impl<'a> ServedAnalyzer<'a> {
+    fn new(settings: &'a Settings) -> Result<ServedAnalyzer<'a>> {
+        let match_files_signature = get_match_files_signature(&settings.data_dir)?;
+        return Ok(ServedAnalyzer {
+            settings,
+            analyzer: create_analyzer(settings)?,
+            match_files_signature,
+        });
+    }
+
+    fn reload_if_changed(&mut self) -> Result<()> {
+        let match_files_signature = get_match_files_signature(&self.settings.data_dir)?;
+        if match_files_signature != self.match_files_signature {
+            info!(count = match_files_signature.0, "Reloading new match files");
+            self.analyzer = create_analyzer(self.settings)?;
+            self.match_files_signature = match_files_signature;
+        }
+        return Ok(());
+    }
+}
+
+fn get_match_files_signature(data_directory: &str) -> Result<(usize, Option<String>)> {
+    let files = read_match_file_paths(data_directory)?;
+    let newest_file_name = files.first().map(|file_path| file_path.file_name().to_string_lossy().into_owned());
+    return Ok((files.len(), newest_file_name));
+}
+
+fn create_response(status_code: u16, body: String) -> tiny_http::Response<std::io::Cursor<Vec<u8>>> {
+    let header = tiny_http::Header::from_bytes("Content-Type", "application/json").expect("Valid header");
+    return tiny_http::Response::from_string(body)
+        .with_status_code(status_code)
+        .with_header(header);
+}
+
+fn create_error_response(status_code: u16, message: &str) -> tiny_http::Response<std::io::Cursor<Vec<u8>>> {
+    let body = serde_json::to_string(&ErrorJson { error: String::from(message) }).expect("Serialize error");
+    return create_response(status_code, body);
+}
+
+/// Percent encoded champion names like "Lee%20Sin" from the path
+fn decode_path_segment(segment: &str) -> String {
+    let mut bytes = Vec::new();
+    let mut rest = segment.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        let decoded = if byte == b'%' && tail.len() >= 2 {
+            std::str::from_utf8(&tail[..2]).ok().and_then(|hex| u8::from_str_radix(hex, 16).ok())
+        } else {
+            None
+        };
+        match decoded {
+            Some(decoded) => {
+                bytes.push(decoded);
+                rest = &tail[2..];
+            },
+            None => {
+                bytes.push(byte);
+                rest = tail;
+            },
+        }
+    }
+    return String::from_utf8_lossy(&bytes).into_owned();
+}
+
+fn handle_request(analyzer: &Analyzer, request: &mut tiny_http::Request) -> tiny_http::Response<std::io::Cursor<Vec<u8>>> {
+    let path = String::from(request.url().split('?').next().unwrap_or(""));
+    let method = request.method().clone();
+    debug!(%method, path, "Request");
+    if path == "/champions" {
+        if method != tiny_http::Method::Get {
+            return create_error_response(405, "Use GET");
+        }
+        let json = ChampionListJson::new(&analyzer.get_sorted_champions());
+        return create_response(200, serde_json::to_string(&json).expect("Serialize champions"));
+    } else if let Some(name) = path.strip_prefix("/champions/") {
+        if method != tiny_http::Method::Get {
+            return create_error_response(405, "Use GET");
+        }
+        let name = decode_path_segment(name);
+        let champion = analyzer.get_champion_infos().iter()
+            .find(|(champion_name, _)| champion_name.eq_ignore_ascii_case(&name));
+        return match champion {
+            Some((champion_name, champion_info)) => create_response(200,
+                serde_json::to_string(&ChampionJson::new(champion_name, champion_info)).expect("Serialize champion")),
+            None => create_error_response(404, &format!("No matches with champion {}", name)),
+        };
+    } else if path == "/draft" {
+        if method != tiny_http::Method::Post {
+            return create_error_response(405, "Use POST");
+        }
+        let mut body = String::new();
+        if let Err(error) = request.as_reader().read_to_string(&mut body) {
+            return create_error_response(400, &error.to_string());
+        }
+        let draft_request: DraftRequestJson = match serde_json::from_str(&body) {
+            Ok(draft_request) => draft_request,
+            Err(error) => return create_error_response(400, &error.to_string()),
+        };
+        let picks = analyzer.get_recommended_picks(
+            draft_request.allies.iter().map(String::as_str).collect(),
+            draft_request.enemies.iter().map(String::as_str).collect(),
+            draft_request.bans.iter().map(String::as_str).collect(),
+            draft_request.limit.unwrap_or(DEFAULT_PICK_LIMIT));
+        let json = DraftResponseJson { schema_version: league_think::json_output::SCHEMA_VERSION, picks };
+        return create_response(200, serde_json::to_string(&json).expect("Serialize draft"));
+    } else {
+        return create_error_response(404, "Unknown path");
+    }
+}
+
+pub fn serve(settings: &Settings, args: CommandLineArguments) -> Result<()> {
+    let mut served_analyzer = ServedAnalyzer::new(settings)?;
+    let server = tiny_http::Server::http(&args.address)
+        .map_err(|error| Error::Request { action: format!("listen on {}", args.address), message: error.to_string() })?;
+    info!(address = %args.address, "Serving");
+    for mut request in server.incoming_requests() {
+        if let Err(error) = served_analyzer.reload_if_changed() {
+            warn!(%error, "Cannot reload match files");
+        }
+        let response = handle_request(&served_analyzer.analyzer, &mut request);
+        if let Err(error) = request.respond(response) {
+            warn!(%error, "Cannot send response");
+        }
+    }
+    return Ok(());
+}
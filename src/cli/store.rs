@@ -0,0 +1,19 @@
+use league_think::store::Reader;
+use league_think::error::Result;
+use crate::cli::config::Settings;
+use crate::cli::create_runtime;
+
+pub fn store(settings: &Settings) -> Result<()> {
+    let runtime = create_runtime()?;
+    return runtime.block_on(async {
+        let api_key = settings.get_api_key()?;
+        let mut reader = Reader::new(
+            &api_key,
+            settings.data_dir.clone(),
+            settings.summoner_name.clone(),
+            settings.platform
+        );
+        reader.set_show_progress(!settings.quiet);
+        return reader.read().await;
+    });
+}
@@ -0,0 +1,12 @@
+use league_think::error::Result;
+use league_think::tilt::TiltInfo;
+use crate::cli::analyze::create_analyzer;
+use crate::cli::config::Settings;
+
+pub fn tilt(settings: &Settings) -> Result<()> {
+    let analyzer = create_analyzer(settings)?;
+    print!("{}", analyzer.get_filtered_summary_text());
+    let tilt_info = TiltInfo::new(analyzer.get_match_results(), settings.statistical_significance_threshold);
+    println!("Tilt:\n{}", tilt_info.get_summary_text());
+    return Ok(());
+}
@@ -0,0 +1,208 @@
+use std::collections::HashMap;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::widgets::{Block, Borders, Cell, List, ListItem, ListState, Paragraph, Row, Table};
+use ratatui::{DefaultTerminal, Frame};
+use crate::cli::config::Settings;
+use league_think::analyze::Analyzer;
+use crate::cli::analyze::create_analyzer;
+use league_think::champion_info::ChampionInfo;
+use league_think::error::{Error, Result};
+use league_think::string::{ format_percent, format_ratio };
+use league_think::win_rate_info::WinRateInfo;
+
+#[derive(Clone, Copy, PartialEq)]
+enum SortOrder {
+    Chance,
+    Ratio,
+    Count,
+}
+
+impl SortOrder {
+    fn next(self) -> SortOrder {
+        return match self {
+            SortOrder::Chance => SortOrder::Ratio,
+            SortOrder::Ratio => SortOrder::Count,
+            SortOrder::Count => SortOrder::Chance,
+        };
+    }
+
+    fn get_name(self) -> &'static str {
+        return match self {
+            SortOrder::Chance => "chance",
+            SortOrder::Ratio => "ratio",
+            SortOrder::Count => "count",
+        };
+    }
+
+    fn sort(self, list: &mut [(&String, &WinRateInfo)]) {
+        match self {
+            SortOrder::Chance => list.sort_by(|a, b| b.1.get_win_chance().partial_cmp(&a.1.get_win_chance()).unwrap()),
+            SortOrder::Ratio => list.sort_by(|a, b| b.1.get_win_rate().partial_cmp(&a.1.get_win_rate()).unwrap()),
+            SortOrder::Count => list.sort_by_key(|item| std::cmp::Reverse(item.1.get_count_of_matches())),
+        }
+    }
+}
+
+struct ChampionBrowser<'a> {
+    /// Most played first
+    champions: Vec<(&'a String, &'a ChampionInfo)>,
+    filter: String,
+    sort_order: SortOrder,
+    list_state: ListState,
+}
+
+impl<'a> ChampionBrowser<'a> {
+    fn new(analyzer: &'a Analyzer) -> ChampionBrowser<'a> {
+        let mut browser = ChampionBrowser {
+            champions: analyzer.get_sorted_champions(),
+            filter: String::new(),
+            sort_order: SortOrder::Chance,
+            list_state: ListState::default(),
+        };
+        browser.list_state.select(Some(0));
+        return browser;
+    }
+
+    fn get_filtered_champions(&self) -> Vec<(&'a String, &'a ChampionInfo)> {
+        let filter = self.filter.to_lowercase();
+        return self.champions.iter()
+            .filter(|(champion_name, _)| champion_name.to_lowercase().contains(&filter))
+            .copied()
+            .collect();
+    }
+
+    fn get_selected_champion(&self) -> Option<(&'a String, &'a ChampionInfo)> {
+        let champions = self.get_filtered_champions();
+        return self.list_state.selected().and_then(|i| champions.get(i).copied());
+    }
+
+    fn move_selection(&mut self, delta: i32) {
+        let count = self.get_filtered_champions().len() as i32;
+        if count == 0 {
+            self.list_state.select(None);
+            return;
+        }
+        let selected = self.list_state.selected().unwrap_or(0) as i32;
+        self.list_state.select(Some((selected + delta).clamp(0, count - 1) as usize));
+    }
+
+    fn set_filter(&mut self, filter: String) {
+        self.filter = filter;
+        self.list_state.select(Some(0));
+        self.move_selection(0);
+    }
+
+    /// Returns false when the user wants to quit
+    fn handle_key(&mut self, code: KeyCode, modifiers: KeyModifiers) -> bool {
+        match code {
+            KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => return false,
+            KeyCode::Esc if self.filter.is_empty() => return false,
+            KeyCode::Esc => self.set_filter(String::new()),
+            KeyCode::Up => self.move_selection(-1),
+            KeyCode::Down => self.move_selection(1),
+            KeyCode::PageUp => self.move_selection(-10),
+            KeyCode::PageDown => self.move_selection(10),
+            KeyCode::Tab => self.sort_order = self.sort_order.next(),
+            KeyCode::Backspace => {
+                let mut filter = self.filter.clone();
+                filter.pop();
+                self.set_filter(filter);
+            },
+            KeyCode::Char(c) => {
+                let mut filter = self.filter.clone();
+                filter.push(c);
+                self.set_filter(filter);
+            },
+            _ => {}
+        }
+        return true;
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Length(32), Constraint::Min(0)])
+            .split(frame.area());
+        let left = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(3), Constraint::Min(0)])
+            .split(columns[0]);
+        let right = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(columns[1]);
+
+        let filter = Paragraph::new(self.filter.as_str())
+            .block(Block::default().borders(Borders::ALL).title("Filter (type, Esc clears)"));
+        frame.render_widget(filter, left[0]);
+
+        let items: Vec<ListItem> = self.get_filtered_champions().iter()
+            .map(|(champion_name, champion_info)|
+                ListItem::new(format!("{:<20} {:>5}", champion_name, champion_info.count_of_matches)))
+            .collect();
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).title("Champions"))
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(list, left[1], &mut self.list_state);
+
+        if let Some((champion_name, champion_info)) = self.get_selected_champion() {
+            let title = format!("{}: allies, sorted by {} (Tab)", champion_name, self.sort_order.get_name());
+            self.draw_table(frame, right[0], &title, champion_info.get_win_rates_with_champions());
+            let title = format!("{}: enemies, sorted by {} (Tab)", champion_name, self.sort_order.get_name());
+            self.draw_table(frame, right[1], &title, champion_info.get_win_rates_vs_champions());
+        }
+    }
+
+    fn draw_table(&self, frame: &mut Frame, area: Rect, title: &str, source: &HashMap<String, WinRateInfo>) {
+        let mut list: Vec<(&String, &WinRateInfo)> = source.iter().collect();
+        self.sort_order.sort(&mut list);
+        let rows: Vec<Row> = list.iter()
+            .map(|(name, info)| {
+                let color = if info.get_win_chance() > 0.5 { Color::Green }
+                    else if info.get_win_chance() < 0.5 { Color::Red }
+                    else { Color::Reset };
+                Row::new(vec![
+                    Cell::from(name.as_str()),
+                    Cell::from(format_percent(info.get_win_chance())).style(Style::default().fg(color)),
+                    Cell::from(format_ratio(info.get_count_of_wins(), info.get_count_of_matches())),
+                    Cell::from(info.get_count_of_matches().to_string()),
+                ])
+            })
+            .collect();
+        let widths = [Constraint::Length(16), Constraint::Length(8), Constraint::Length(8), Constraint::Length(6)];
+        let table = Table::new(rows, widths)
+            .header(Row::new(vec!["champion", "chance", "ratio", "count"])
+                .style(Style::default().add_modifier(Modifier::BOLD)))
+            .block(Block::default().borders(Borders::ALL).title(title));
+        frame.render_widget(table, area);
+    }
+}
+
+fn run(terminal: &mut DefaultTerminal, browser: &mut ChampionBrowser) -> std::io::Result<()> {
+    loop {
+        terminal.draw(|frame| browser.draw(frame))?;
+        if let Event::Key(key) = event::read()? {
+            if key.kind == KeyEventKind::Press && !browser.handle_key(key.code, key.modifiers) {
+                return Ok(());
+            }
+        }
+    }
+}
+
+pub fn tui(settings: &Settings) -> Result<()> {
+    let analyzer = create_analyzer(settings)?;
+    let mut browser = ChampionBrowser::new(&analyzer);
+    // Without a terminal, e.g. with redirected input, initializing fails half way and is undone
+    let mut terminal = match ratatui::try_init() {
+        Ok(terminal) => terminal,
+        Err(source) => {
+            ratatui::restore();
+            return Err(Error::Runtime { action: String::from("initialize the terminal"), source });
+        }
+    };
+    let result = run(&mut terminal, &mut browser);
+    ratatui::restore();
+    return result.map_err(|source| Error::Runtime { action: String::from("draw on the terminal"), source });
+}
@@ -0,0 +1,160 @@
+use std::collections::BTreeMap;
+use std::ops::Add;
+use crate::champion_class::ChampionClasses;
+use crate::match_facts::ParticipantFacts;
+use crate::string::INDENTATION_STRING;
+use crate::win_rate_info::{WinRateInfo, DEFAULT_SIGNIFICANCE_THRESHOLD};
+
+/// Share of one damage type above which a team counts as lopsided.
+const LOPSIDED_DAMAGE_SHARE: f32 = 0.7;
+const TANK_CLASS: &str = "Tank";
+const FIGHTER_CLASS: &str = "Fighter";
+/// Only a user-edited champion classes file has this class, see `ChampionClasses`
+const ENGAGE_CLASS: &str = "Engage";
+
+pub struct TeamComposition {
+    pub physical_damage: i64,
+    pub magic_damage: i64,
+    /// `None` when no champion of the team has known classes
+    pub count_of_tanks: Option<i32>,
+    /// Tanks and fighters
+    pub count_of_frontliners: Option<i32>,
+    /// `None` also when the champion classes have no engage class at all
+    pub count_of_engagers: Option<i32>,
+}
+
+impl TeamComposition {
+    pub fn new(team: &[&ParticipantFacts], champion_classes: &ChampionClasses) -> TeamComposition {
+        let mut composition = TeamComposition {
+            physical_damage: 0,
+            magic_damage: 0,
+            count_of_tanks: None,
+            count_of_frontliners: None,
+            count_of_engagers: None,
+        };
+        let has_engage_class = champion_classes.has_class(ENGAGE_CLASS);
+        for participant in team {
+            composition.physical_damage += participant.physical_damage;
+            composition.magic_damage += participant.magic_damage;
+            let classes = champion_classes.get_classes(&participant.champion_name);
+            if classes.is_empty() {
+                continue;
+            }
+            let is_tank = classes.iter().any(|class_name| class_name == TANK_CLASS);
+            let is_fighter = classes.iter().any(|class_name| class_name == FIGHTER_CLASS);
+            *composition.count_of_tanks.get_or_insert(0) += is_tank as i32;
+            *composition.count_of_frontliners.get_or_insert(0) += (is_tank || is_fighter) as i32;
+            if has_engage_class {
+                let is_engager = classes.iter().any(|class_name| class_name == ENGAGE_CLASS);
+                *composition.count_of_engagers.get_or_insert(0) += is_engager as i32;
+            }
+        }
+        return composition;
+    }
+
+    /// Physical share of the damage dealt to champions, `None` if the team dealt no damage at all
+    pub fn get_physical_share(&self) -> Option<f32> {
+        let total = self.physical_damage + self.magic_damage;
+        if total > 0 {
+            return Some((self.physical_damage as f32) / (total as f32));
+        } else {
+            return None;
+        }
+    }
+
+    pub fn get_damage_profile(&self) -> &'static str {
+        return match self.get_physical_share() {
+            Some(share) if share > LOPSIDED_DAMAGE_SHARE => ">70% AD",
+            Some(share) if 1.0 - share > LOPSIDED_DAMAGE_SHARE => ">70% AP",
+            Some(_) => "mixed damage",
+            None => "no damage",
+        };
+    }
+
+    fn format_count(count: i32, singular: &str, plural: &str) -> String {
+        return if count >= 2 {
+            format!("2+ {}", plural)
+        } else if count == 1 {
+            format!("1 {}", singular)
+        } else {
+            format!("0 {}", plural)
+        };
+    }
+}
+
+/// Win rates of the tracked player by features of both team compositions
+pub struct CompositionInfo {
+    win_rates: BTreeMap<String, BTreeMap<String, WinRateInfo>>,
+    /// Given to every win rate of a composition feature
+    significance_threshold: i32,
+}
+
+impl CompositionInfo {
+    pub fn new() -> CompositionInfo {
+        return CompositionInfo::with_significance_threshold(DEFAULT_SIGNIFICANCE_THRESHOLD);
+    }
+
+    pub fn with_significance_threshold(significance_threshold: i32) -> CompositionInfo {
+        return CompositionInfo {
+            win_rates: BTreeMap::new(),
+            significance_threshold,
+        }
+    }
+
+    pub fn add(&mut self, my_team: &TeamComposition, enemy_team: &TeamComposition, win: bool) {
+        self.add_feature("my team damage", my_team.get_damage_profile(), win);
+        self.add_feature("enemy team damage", enemy_team.get_damage_profile(), win);
+        if let Some(count) = my_team.count_of_tanks {
+            self.add_feature("my team tanks", &TeamComposition::format_count(count, "tank", "tanks"), win);
+        }
+        if let Some(count) = enemy_team.count_of_tanks {
+            self.add_feature("enemy team tanks", &TeamComposition::format_count(count, "tank", "tanks"), win);
+        }
+        if let Some(count) = my_team.count_of_frontliners {
+            self.add_feature("my team frontline",
+                &TeamComposition::format_count(count, "frontliner", "frontliners"), win);
+        }
+        if let Some(count) = enemy_team.count_of_frontliners {
+            self.add_feature("enemy team frontline",
+                &TeamComposition::format_count(count, "frontliner", "frontliners"), win);
+        }
+        if let Some(count) = my_team.count_of_engagers {
+            self.add_feature("my team engage", &TeamComposition::format_count(count, "engager", "engagers"), win);
+        }
+        if let Some(count) = enemy_team.count_of_engagers {
+            self.add_feature("enemy team engage", &TeamComposition::format_count(count, "engager", "engagers"), win);
+        }
+    }
+
+    fn add_feature(&mut self, feature: &str, value: &str, win: bool) {
+        let significance_threshold = self.significance_threshold;
+        self.win_rates
+            .entry(String::from(feature)).or_default()
+            .entry(String::from(value))
+            .or_insert_with(|| WinRateInfo::with_significance_threshold(significance_threshold))
+            .add(win);
+    }
+
+    pub fn get_win_rates(&self) -> &BTreeMap<String, BTreeMap<String, WinRateInfo>> {
+        return &self.win_rates;
+    }
+
+    pub fn get_summary_text(&self) -> String {
+        let mut text = String::new();
+        for (feature, values) in &self.win_rates {
+            let list: Vec<(&str, &WinRateInfo)> = values.iter()
+                .map(|(value, win_rate_info)| (value.as_str(), win_rate_info))
+                .collect();
+            text = text
+                .add(feature).add(":\n")
+                .add(&WinRateInfo::format_list_of_named(&list, INDENTATION_STRING));
+        }
+        return text;
+    }
+}
+
+impl Default for CompositionInfo {
+    fn default() -> CompositionInfo {
+        return CompositionInfo::new();
+    }
+}
@@ -0,0 +1,130 @@
+use std::ops::Add;
+use crate::string::{ colorize, format_percent, format_ratio_detailed, TableCell, TextColor, TextTable, INDENTATION_STRING };
+use crate::win_rate_info::WinRateInfo;
+
+/// Score of one ally or enemy champion for the champion I might pick
+#[derive(serde::Serialize)]
+pub struct MatchupScore {
+    pub name: String,
+    /// Recorded games of the matchup; absent when only the class prior is known
+    pub wins: Option<i32>,
+    pub matches: Option<i32>,
+    /// Class-level chance which the matchup leans towards when it has too few games
+    pub class_prior: Option<f32>,
+    pub score: f32,
+}
+
+impl MatchupScore {
+    /// Matchups with too few games lean towards the class-level chance when the classes are known
+    pub fn new(name: &str, info: Option<&WinRateInfo>, class_prior: Option<f32>) -> Option<MatchupScore> {
+        let score = match (info, class_prior) {
+            (Some(info), Some(class_prior)) if !info.is_significant() => info.get_win_chance_with_prior(class_prior),
+            (Some(info), _) => info.get_win_rate(),
+            (None, Some(class_prior)) => class_prior,
+            (None, None) => return None,
+        };
+        let class_prior = match info {
+            Some(info) if info.is_significant() => None,
+            _ => class_prior,
+        };
+        return Some(MatchupScore {
+            name: String::from(name),
+            wins: info.map(|info| info.get_count_of_wins()),
+            matches: info.map(|info| info.get_count_of_matches()),
+            class_prior,
+            score,
+        });
+    }
+
+    fn get_ratio_text(&self) -> String {
+        return match (self.wins, self.matches) {
+            (Some(wins), Some(matches)) => format_ratio_detailed(wins, matches),
+            _ => String::new(),
+        };
+    }
+
+    fn get_class_prior_text(&self) -> String {
+        return match (self.matches, self.class_prior) {
+            (Some(_), Some(class_prior)) => format!("(class prior {})", format_percent(class_prior)),
+            (None, Some(class_prior)) => String::from("class prior ").add(&format_percent(class_prior)),
+            (_, None) => String::new(),
+        };
+    }
+}
+
+/// How good picking `champion` looks against the given draft
+#[derive(serde::Serialize)]
+pub struct DraftScore {
+    pub champion: String,
+    pub count_of_matches: i32,
+    /// Average score of the allies, absent when none of them has data
+    pub ally_strength: Option<f32>,
+    /// Average score of the enemies, absent when none of them has data
+    pub enemy_weakness: Option<f32>,
+    /// Average score of all allies and enemies together
+    pub summary_chance: Option<f32>,
+    pub allies: Vec<MatchupScore>,
+    pub enemies: Vec<MatchupScore>,
+}
+
+impl DraftScore {
+    pub fn new(champion: &str, count_of_matches: i32, allies: Vec<MatchupScore>, enemies: Vec<MatchupScore>) -> DraftScore {
+        let all: Vec<&MatchupScore> = allies.iter().chain(enemies.iter()).collect();
+        return DraftScore {
+            champion: String::from(champion),
+            count_of_matches,
+            ally_strength: DraftScore::get_average(allies.iter()),
+            enemy_weakness: DraftScore::get_average(enemies.iter()),
+            summary_chance: DraftScore::get_average(all.into_iter()),
+            allies,
+            enemies,
+        }
+    }
+
+    fn get_average<'a>(scores: impl Iterator<Item = &'a MatchupScore>) -> Option<f32> {
+        let scores: Vec<f32> = scores.map(|score| score.score).collect();
+        if scores.is_empty() {
+            return None;
+        }
+        return Some(scores.iter().sum::<f32>() / (scores.len() as f32));
+    }
+
+    fn format_chance(chance: Option<f32>, count: usize) -> String {
+        return match chance {
+            Some(chance) => colorize(&format!("{} of {}", format_percent(chance), count),
+                TextColor::from_win_chance(chance)),
+            None => String::from("[?]"),
+        };
+    }
+
+    fn format_breakdown(scores: &[MatchupScore], indentation: &str) -> String {
+        let mut table = TextTable::new();
+        for score in scores {
+            table.add_row(vec![
+                TableCell::new(score.name.clone()),
+                TableCell::colored(score.get_ratio_text(), TextColor::from_win_chance(score.score)),
+                TableCell::colored(score.get_class_prior_text(),
+                    if score.matches.is_none() { TextColor::from_win_chance(score.score) } else { None }),
+            ]);
+        }
+        return table.format(indentation);
+    }
+
+    pub fn get_summary_text(&self) -> String {
+        let mut text = format!("{}: ally strength {}, enemy weakness {}, summary chance {}",
+            self.champion,
+            DraftScore::format_chance(self.ally_strength, self.allies.len()),
+            DraftScore::format_chance(self.enemy_weakness, self.enemies.len()),
+            DraftScore::format_chance(self.summary_chance, self.allies.len() + self.enemies.len())
+        );
+        text.push('\n');
+        text.push_str(INDENTATION_STRING);
+        text.push_str("Allies:\n");
+        let breakdown_indentation = INDENTATION_STRING.repeat(2);
+        text.push_str(&DraftScore::format_breakdown(&self.allies, &breakdown_indentation));
+        text.push_str(INDENTATION_STRING);
+        text.push_str("Enemies:\n");
+        text.push_str(&DraftScore::format_breakdown(&self.enemies, &breakdown_indentation));
+        return text;
+    }
+}
@@ -0,0 +1,153 @@
+use std::collections::HashMap;
+use std::ops::Add;
+use tracing::{info, warn};
+use crate::analyze::read_match_facts;
+use crate::champion_info::{ChampionInfo, DEFAULT_SUMMARY_LIMIT};
+use crate::error::{Error, Result};
+use crate::match_facts::Abnormality;
+use crate::win_rate_info::{WinRateInfo, DEFAULT_SIGNIFICANCE_THRESHOLD};
+
+/// Win rates of two players in the matches where they played on the same team
+pub struct DuoAnalyzer {
+    duration_limit: chrono::Duration,
+    data_directory: String,
+    show_progress: bool,
+    /// Summoner id, puuid or name of each player
+    players: [String; 2],
+    /// Names as in the latest shared match
+    player_names: [String; 2],
+    win_rate: WinRateInfo,
+    /// By the pair of champions and roles, e.g. "Ahri + Lee Sin (MIDDLE + JUNGLE)"
+    win_rates_by_pick: HashMap<String, WinRateInfo>,
+    skipped_files: Vec<Error>,
+    exclude_early_surrenders: bool,
+    significance_threshold: i32,
+    /// Length of the best and worst pick lists
+    summary_limit: usize,
+    count_of_filtered_matches: usize,
+}
+
+impl DuoAnalyzer {
+    pub fn new(data_directory: String, first_player: String, second_player: String) -> DuoAnalyzer {
+        return DuoAnalyzer {
+            duration_limit: chrono::Duration::days(0),
+            data_directory,
+            show_progress: false,
+            player_names: [first_player.clone(), second_player.clone()],
+            players: [first_player, second_player],
+            win_rate: WinRateInfo::new(),
+            win_rates_by_pick: HashMap::new(),
+            skipped_files: Vec::new(),
+            exclude_early_surrenders: false,
+            count_of_filtered_matches: 0,
+            significance_threshold: DEFAULT_SIGNIFICANCE_THRESHOLD,
+            summary_limit: DEFAULT_SUMMARY_LIMIT,
+        };
+    }
+
+    /// Matches older than `duration_limit` before the latest match are ignored
+    pub fn set_duration_limit(&mut self, duration_limit: chrono::Duration) {
+        self.duration_limit = duration_limit;
+    }
+
+    pub fn set_show_progress(&mut self, show_progress: bool) {
+        self.show_progress = show_progress;
+    }
+
+    pub fn set_exclude_early_surrenders(&mut self, exclude_early_surrenders: bool) {
+        self.exclude_early_surrenders = exclude_early_surrenders;
+    }
+
+    pub fn set_significance_threshold(&mut self, significance_threshold: i32) {
+        self.significance_threshold = significance_threshold;
+    }
+
+    pub fn set_summary_limit(&mut self, summary_limit: usize) {
+        self.summary_limit = summary_limit;
+    }
+
+    /// Reads the match facts through the same batched and cached pipeline as `Analyzer`
+    pub fn analyze_files(&mut self) -> Result<()> {
+        self.win_rate = WinRateInfo::with_significance_threshold(self.significance_threshold);
+        self.win_rates_by_pick.clear();
+        self.skipped_files.clear();
+        self.count_of_filtered_matches = 0;
+        let data_directory = self.data_directory.clone();
+        let mut has_names = false;
+        let mut is_player_found = [false, false];
+        let summary = read_match_facts(&data_directory, self.duration_limit, self.show_progress,
+            "Analyzing shared matches", |facts, _, _| {
+                let first = facts.participants.iter().find(|participant| participant.is_player(&self.players[0]));
+                let second = facts.participants.iter().find(|participant| participant.is_player(&self.players[1]));
+                is_player_found[0] |= first.is_some();
+                is_player_found[1] |= second.is_some();
+                let (first, second) = match (first, second) {
+                    (Some(first), Some(second)) if first.team_id == second.team_id => (first, second),
+                    _ => return,
+                };
+                if !has_names {
+                    self.player_names = [first.name.clone(), second.name.clone()];
+                    has_names = true;
+                }
+                match facts.abnormality {
+                    Some(Abnormality::EarlySurrender) if !self.exclude_early_surrenders => {},
+                    Some(_) => {
+                        self.count_of_filtered_matches += 1;
+                        return;
+                    },
+                    None => {},
+                }
+                self.win_rate.add(first.win);
+                let pick = format!("{} + {} ({} + {})",
+                    first.champion_name, second.champion_name, first.role, second.role);
+                let significance_threshold = self.significance_threshold;
+                self.win_rates_by_pick.entry(pick)
+                    .or_insert_with(|| WinRateInfo::with_significance_threshold(significance_threshold))
+                    .add(first.win);
+            })?;
+        info!(total_files = summary.total_files, processed_files = summary.processed_files,
+            parsed_files = summary.parsed_files, shared_matches = self.win_rate.get_count_of_matches(),
+            skipped_files = summary.skipped_files.len(), filtered_matches = self.count_of_filtered_matches,
+            "Duo analysis complete");
+        for (player, is_found) in self.players.iter().zip(is_player_found) {
+            if !is_found {
+                warn!(%player, "Player is in none of the analyzed matches");
+            }
+        }
+        self.skipped_files = summary.skipped_files;
+        return Ok(());
+    }
+
+    /// Shared matches left out as remakes, AFK games or early surrenders
+    pub fn get_count_of_filtered_matches(&self) -> usize {
+        return self.count_of_filtered_matches;
+    }
+
+    pub fn get_win_rate(&self) -> &WinRateInfo {
+        return &self.win_rate;
+    }
+
+    pub fn get_win_rates_by_pick(&self) -> &HashMap<String, WinRateInfo> {
+        return &self.win_rates_by_pick;
+    }
+
+    pub fn get_skipped_files(&self) -> &[Error] {
+        return &self.skipped_files;
+    }
+
+    pub fn get_summary_text(&self) -> String {
+        let mut text = String::new();
+        text = text
+            .add(&self.player_names[0]).add(" and ").add(&self.player_names[1])
+            .add(": ").add(&self.win_rate.to_string());
+        text.push('\n');
+        if self.count_of_filtered_matches > 0 {
+            text = text.add("filtered matches: ").add(&self.count_of_filtered_matches.to_string());
+            text.push('\n');
+        }
+        let picks = ChampionInfo::get_significant_list(&self.win_rates_by_pick);
+        text = text.add(&ChampionInfo::format_top_summary_list("best picks", &picks, true, self.summary_limit));
+        text = text.add(&ChampionInfo::format_top_summary_list("worst picks", &picks, false, self.summary_limit));
+        return text;
+    }
+}
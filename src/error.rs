@@ -0,0 +1,89 @@
+use std::fmt;
+
+/// Process exit codes of the `league_think` executable, one per kind of failure
+pub const EXIT_CODE_FAILURE: i32 = 1;
+pub const EXIT_CODE_MISSING_FILE: i32 = 3;
+pub const EXIT_CODE_INVALID_INPUT: i32 = 4;
+pub const EXIT_CODE_API_FAILURE: i32 = 5;
+pub const EXIT_CODE_RUNTIME_FAILURE: i32 = 6;
+
+#[derive(Debug)]
+pub enum Error {
+    /// A required file such as the Riot API key or the summoner id file does not exist
+    MissingFile { file_path: String, source: std::io::Error },
+    /// Reading or writing a file which exists failed
+    Io { file_path: String, source: std::io::Error },
+    InvalidMatchJson { file_path: String, source: serde_json::Error },
+    InvalidChampionClasses { file_path: String, source: serde_json::Error },
+    InvalidConfig { file_path: String, message: String },
+    InvalidRankHistory { file_path: String, source: serde_json::Error },
+    UnknownProfile(String),
+    UnknownPlatform(String),
+    Api { action: String, source: Box<riven::RiotApiError> },
+    /// A request made without `riven`
+    Request { action: String, message: String },
+    SummonerNotFound(String),
+    MatchNotFound(String),
+    /// Something the program needs besides files, such as the async runtime or the terminal, failed
+    Runtime { action: String, source: std::io::Error },
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+    /// Missing files are reported as such, every other failure to read a file is an I/O error
+    pub fn from_io(file_path: &str, source: std::io::Error) -> Error {
+        let file_path = String::from(file_path);
+        if source.kind() == std::io::ErrorKind::NotFound {
+            return Error::MissingFile { file_path, source };
+        } else {
+            return Error::Io { file_path, source };
+        }
+    }
+
+    pub fn get_exit_code(&self) -> i32 {
+        return match self {
+            Error::MissingFile { .. } => EXIT_CODE_MISSING_FILE,
+            Error::Io { .. } => EXIT_CODE_FAILURE,
+            Error::InvalidMatchJson { .. } | Error::InvalidChampionClasses { .. } | Error::InvalidConfig { .. } |
+                Error::InvalidRankHistory { .. } | Error::UnknownProfile(_) | Error::UnknownPlatform(_) => EXIT_CODE_INVALID_INPUT,
+            Error::Api { .. } | Error::Request { .. } | Error::SummonerNotFound(_) | Error::MatchNotFound(_) => EXIT_CODE_API_FAILURE,
+            Error::Runtime { .. } => EXIT_CODE_RUNTIME_FAILURE,
+        };
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return match self {
+            Error::MissingFile { file_path, .. } => write!(f, "Need file: {}", file_path),
+            Error::Io { file_path, source } => write!(f, "Cannot access {}: {}", file_path, source),
+            Error::InvalidMatchJson { file_path, source } => write!(f, "Invalid match file {}: {}", file_path, source),
+            Error::InvalidChampionClasses { file_path, source } =>
+                write!(f, "Invalid champion classes file {}: {}", file_path, source),
+            Error::InvalidConfig { file_path, message } =>
+                write!(f, "Invalid configuration file {}: {}", file_path, message),
+            Error::InvalidRankHistory { file_path, source } =>
+                write!(f, "Invalid rank history file {}: {}", file_path, source),
+            Error::UnknownProfile(name) => write!(f, "Profile {} is not in the configuration file", name),
+            Error::UnknownPlatform(name) => write!(f, "Unknown platform {}", name),
+            Error::Api { action, source } => write!(f, "Riot API request failed: {}: {}", action, source),
+            Error::Request { action, message } => write!(f, "Request failed: {}: {}", action, message),
+            Error::SummonerNotFound(name) => write!(f, "Summoner {} does not exist", name),
+            Error::MatchNotFound(id) => write!(f, "Match {} does not exist", id),
+            Error::Runtime { action, source } => write!(f, "Cannot {}: {}", action, source),
+        };
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        return match self {
+            Error::MissingFile { source, .. } | Error::Io { source, .. } | Error::Runtime { source, .. } => Some(source),
+            Error::InvalidMatchJson { source, .. } | Error::InvalidChampionClasses { source, .. } |
+                Error::InvalidRankHistory { source, .. } => Some(source),
+            Error::Api { source, .. } => Some(source.as_ref()),
+            _ => None,
+        };
+    }
+}
@@ -0,0 +1,238 @@
+//! Machine readable output of `analyze --format json` and of the `serve` HTTP API.
+//!
+//! The schema is versioned by `SCHEMA_VERSION`: fields may be added within a version,
+//! but renaming or removing a field requires increasing it.
+//!
+//! ```text
+//! {
+//!   "schema_version": 1,
+//!   "champions": [ChampionJson],         // my champions, most played first
+//!   "composition": { "<feature>": [WinRateJson] },
+//!   "filtered_matches": { "remake" | "afk" | "early_surrender": count },   // only kinds that occurred
+//!   "players": [PlayerJson],             // recurring teammates and opponents, most often met first
+//!   "draft": [DraftScore] | null          // only when --allies or --enemies is given
+//! }
+//! ChampionJson = { "name", "count_of_matches", "allies": [WinRateJson], "enemies": [WinRateJson],
+//!                  "ally_classes": [WinRateJson], "enemy_classes": [WinRateJson],
+//!                  "sides": [WinRateJson], "game_lengths": [WinRateJson] }
+//! WinRateJson = { "name", "wins", "matches", "win_rate", "win_chance" }
+//! PlayerJson = { "puuid", "name", "with": RecordJson, "against": RecordJson }
+//! RecordJson = { "wins", "matches", "win_rate", "win_chance" }   // my record with or against the player
+//! DraftScore = { "champion", "count_of_matches", "ally_strength", "enemy_weakness", "summary_chance",
+//!                "allies": [MatchupScore], "enemies": [MatchupScore] }
+//! MatchupScore = { "name", "wins", "matches", "class_prior", "score" }
+//! ```
+//!
+//! The HTTP API of `serve` answers with the same types:
+//!
+//! ```text
+//! GET /champions          -> { "schema_version", "champions": [ChampionJson] }
+//! GET /champions/{name}   -> ChampionJson
+//! POST /draft             <- { "allies": [name], "enemies": [name], "bans": [name], "limit": 5 }, all optional
+//!                         -> { "schema_version", "picks": [DraftScore] }   // best pick first
+//! errors                  -> { "error": "<message>" }
+//! ```
+//!
+//! Rates, chances and scores are fractions between 0 and 1; optional values are `null`.
+//! Matchup lists are ordered by count of matches, most played first; composition values by name;
+//! sides go blue then red, game lengths shortest first.
+
+use std::collections::{BTreeMap, HashMap};
+use crate::champion_info::{ChampionInfo, GAME_LENGTH_NAMES, SIDE_NAMES};
+use crate::composition::CompositionInfo;
+use crate::match_facts::Abnormality;
+use crate::player_info::{PlayerInfo, RECURRING_PLAYER_THRESHOLD};
+use crate::draft_score::DraftScore;
+use crate::win_rate_info::WinRateInfo;
+
+pub const SCHEMA_VERSION: i32 = 1;
+
+#[derive(serde::Serialize)]
+pub struct WinRateJson {
+    pub name: String,
+    pub wins: i32,
+    pub matches: i32,
+    pub win_rate: f32,
+    /// Win rate adjusted towards 50% for small samples
+    pub win_chance: f32,
+}
+
+impl WinRateJson {
+    pub fn new(name: &str, info: &WinRateInfo) -> WinRateJson {
+        return WinRateJson {
+            name: String::from(name),
+            wins: info.get_count_of_wins(),
+            matches: info.get_count_of_matches(),
+            win_rate: info.get_win_rate(),
+            win_chance: info.get_win_chance(),
+        }
+    }
+
+    /// Keeps the order of `names` and leaves out names without matches
+    pub fn from_ordered_map(source: &HashMap<String, WinRateInfo>, names: &[&str]) -> Vec<WinRateJson> {
+        return names.iter()
+            .filter_map(|name| source.get(*name).map(|info| WinRateJson::new(name, info)))
+            .collect();
+    }
+
+    pub fn from_map(source: &HashMap<String, WinRateInfo>) -> Vec<WinRateJson> {
+        let mut list: Vec<WinRateJson> = source.iter()
+            .map(|(name, info)| WinRateJson::new(name, info))
+            .collect();
+        list.sort_by(|a, b| b.matches.cmp(&a.matches).then_with(|| a.name.cmp(&b.name)));
+        return list;
+    }
+}
+
+#[derive(serde::Serialize)]
+pub struct ChampionJson {
+    pub name: String,
+    pub count_of_matches: i32,
+    pub allies: Vec<WinRateJson>,
+    pub enemies: Vec<WinRateJson>,
+    pub ally_classes: Vec<WinRateJson>,
+    pub enemy_classes: Vec<WinRateJson>,
+    pub sides: Vec<WinRateJson>,
+    pub game_lengths: Vec<WinRateJson>,
+}
+
+impl ChampionJson {
+    pub fn new(name: &str, info: &ChampionInfo) -> ChampionJson {
+        return ChampionJson {
+            name: String::from(name),
+            count_of_matches: info.count_of_matches,
+            allies: WinRateJson::from_map(info.get_win_rates_with_champions()),
+            enemies: WinRateJson::from_map(info.get_win_rates_vs_champions()),
+            ally_classes: WinRateJson::from_map(info.get_win_rates_with_classes()),
+            enemy_classes: WinRateJson::from_map(info.get_win_rates_vs_classes()),
+            sides: WinRateJson::from_ordered_map(info.get_win_rates_by_side(), &SIDE_NAMES),
+            game_lengths: WinRateJson::from_ordered_map(info.get_win_rates_by_game_length(), &GAME_LENGTH_NAMES),
+        }
+    }
+}
+
+#[derive(serde::Serialize)]
+pub struct RecordJson {
+    pub wins: i32,
+    pub matches: i32,
+    pub win_rate: f32,
+    pub win_chance: f32,
+}
+
+impl RecordJson {
+    pub fn new(info: &WinRateInfo) -> RecordJson {
+        return RecordJson {
+            wins: info.get_count_of_wins(),
+            matches: info.get_count_of_matches(),
+            win_rate: info.get_win_rate(),
+            win_chance: info.get_win_chance(),
+        };
+    }
+}
+
+#[derive(serde::Serialize)]
+pub struct PlayerJson {
+    pub puuid: String,
+    /// Riot ID or summoner name of the latest match with this player
+    pub name: String,
+    pub with: RecordJson,
+    pub against: RecordJson,
+}
+
+impl PlayerJson {
+    /// Players met at least `RECURRING_PLAYER_THRESHOLD` times as teammate or opponent, most often met first
+    pub fn from_map(player_infos: &HashMap<String, PlayerInfo>) -> Vec<PlayerJson> {
+        let mut players: Vec<(&String, &PlayerInfo)> = player_infos.iter()
+            .filter(|(_, info)| info.get_record_with().get_count_of_matches() >= RECURRING_PLAYER_THRESHOLD ||
+                info.get_record_vs().get_count_of_matches() >= RECURRING_PLAYER_THRESHOLD)
+            .collect();
+        let get_count_of_matches = |info: &PlayerInfo|
+            info.get_record_with().get_count_of_matches() + info.get_record_vs().get_count_of_matches();
+        players.sort_by(|a, b| get_count_of_matches(b.1).cmp(&get_count_of_matches(a.1))
+            .then_with(|| a.1.name.cmp(&b.1.name)));
+        return players.into_iter()
+            .map(|(puuid, info)| PlayerJson {
+                puuid: puuid.clone(),
+                name: info.name.clone(),
+                with: RecordJson::new(info.get_record_with()),
+                against: RecordJson::new(info.get_record_vs()),
+            })
+            .collect();
+    }
+}
+
+#[derive(serde::Serialize)]
+pub struct AnalysisJson<'a> {
+    pub schema_version: i32,
+    pub champions: Vec<ChampionJson>,
+    pub composition: BTreeMap<String, Vec<WinRateJson>>,
+    /// Count of my matches left out by abnormality
+    pub filtered_matches: BTreeMap<String, usize>,
+    pub players: Vec<PlayerJson>,
+    pub draft: Option<&'a [DraftScore]>,
+}
+
+impl<'a> AnalysisJson<'a> {
+    pub fn new(champions: &[(&String, &ChampionInfo)], composition_info: &CompositionInfo,
+            filtered_matches: &HashMap<Abnormality, usize>, player_infos: &HashMap<String, PlayerInfo>,
+            draft: Option<&'a [DraftScore]>) -> AnalysisJson<'a> {
+        let mut composition = BTreeMap::new();
+        for (feature, values) in composition_info.get_win_rates() {
+            let values: Vec<WinRateJson> = values.iter()
+                .map(|(value, info)| WinRateJson::new(value, info))
+                .collect();
+            composition.insert(feature.clone(), values);
+        }
+        return AnalysisJson {
+            schema_version: SCHEMA_VERSION,
+            champions: champions.iter()
+                .map(|(name, info)| ChampionJson::new(name, info))
+                .collect(),
+            composition,
+            filtered_matches: filtered_matches.iter()
+                .map(|(abnormality, count)| (String::from(abnormality.get_key()), *count))
+                .collect(),
+            players: PlayerJson::from_map(player_infos),
+            draft,
+        }
+    }
+}
+
+#[derive(serde::Serialize)]
+pub struct ChampionListJson {
+    pub schema_version: i32,
+    pub champions: Vec<ChampionJson>,
+}
+
+impl ChampionListJson {
+    pub fn new(champions: &[(&String, &ChampionInfo)]) -> ChampionListJson {
+        return ChampionListJson {
+            schema_version: SCHEMA_VERSION,
+            champions: champions.iter()
+                .map(|(name, info)| ChampionJson::new(name, info))
+                .collect(),
+        };
+    }
+}
+
+#[derive(serde::Deserialize)]
+pub struct DraftRequestJson {
+    #[serde(default)]
+    pub allies: Vec<String>,
+    #[serde(default)]
+    pub enemies: Vec<String>,
+    #[serde(default)]
+    pub bans: Vec<String>,
+    pub limit: Option<usize>,
+}
+
+#[derive(serde::Serialize)]
+pub struct DraftResponseJson {
+    pub schema_version: i32,
+    pub picks: Vec<DraftScore>,
+}
+
+#[derive(serde::Serialize)]
+pub struct ErrorJson {
+    pub error: String,
+}
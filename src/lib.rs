@@ -0,0 +1,30 @@
+//! Statistics of League of Legends ranked matches stored as match-v5 JSON files.
+//!
+//! Build an [`analyze::Analyzer`] over a data directory, call [`analyze::Analyzer::analyze_files`],
+//! then read [`champion_info::ChampionInfo`] and [`win_rate_info::WinRateInfo`] values from it
+//! or score a draft with [`analyze::Analyzer::get_draft_scores`].
+
+// Explicit `return` statements are the house style of this crate.
+#![allow(clippy::needless_return)]
+
+pub mod analyze;
+pub mod champ_select;
+pub mod champion_class;
+pub mod champion_info;
+pub mod composition;
+pub mod draft_score;
+pub mod duo;
+pub mod error;
+pub mod json_output;
+pub mod live;
+pub mod match_cache;
+pub mod match_facts;
+pub mod match_model;
+pub mod player_info;
+pub mod pool;
+pub mod progress;
+pub mod rank;
+pub mod store;
+pub mod string;
+pub mod tilt;
+pub mod win_rate_info;
@@ -0,0 +1,97 @@
+//! Champions of the game I am playing right now, read from spectator-v5.
+//!
+//! `riven` 2.6 only knows spectator-v4, so the request is made directly. The base URL can point
+//! to a local mock server instead of `https://{platform}.api.riotgames.com`.
+
+use serde::Deserialize;
+use tracing::warn;
+use crate::error::{Error, Result};
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CurrentGameParticipant {
+    #[serde(default)]
+    puuid: String,
+    champion_id: i64,
+    team_id: i64,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CurrentGameInfo {
+    participants: Vec<CurrentGameParticipant>,
+}
+
+/// Champion names of a game in progress, as used by `Analyzer::get_draft_scores`
+pub struct LiveDraft {
+    /// `None` until I picked
+    pub my_champion: Option<String>,
+    /// My teammates, without me
+    pub allies: Vec<String>,
+    pub enemies: Vec<String>,
+}
+
+/// Identifier like in match files, e.g. "MonkeyKing"; `None` for champions newer than the `riven` champion table,
+/// whose id must not be corrected to the name of some other champion
+pub fn find_champion_name(champion_id: i64) -> Option<String> {
+    return riven::consts::Champion(champion_id as i16).identifier().map(String::from);
+}
+
+pub fn get_default_base_url(platform: riven::consts::PlatformRoute) -> String {
+    return format!("https://{}.api.riotgames.com", platform.to_string().to_lowercase());
+}
+
+/// `None` when I am not in a game
+pub async fn read_live_draft(api_key: &str, base_url: &str, puuid: &str) -> Result<Option<LiveDraft>> {
+    let url = format!("{}/lol/spectator/v5/active-games/by-summoner/{}", base_url.trim_end_matches('/'), puuid);
+    let request_error = |error: reqwest::Error| Error::Request {
+        action: String::from("read live game"),
+        message: error.to_string(),
+    };
+    let response = reqwest::Client::new().get(&url)
+        .header("X-Riot-Token", api_key.trim())
+        .send().await
+        .map_err(request_error)?;
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    let game: CurrentGameInfo = response.error_for_status()
+        .map_err(request_error)?
+        .json().await
+        .map_err(request_error)?;
+
+    let me = game.participants.iter().find(|participant| participant.puuid == puuid);
+    let my_team_id = match me {
+        Some(me) => me.team_id,
+        None => return Err(Error::Request {
+            action: String::from("read live game"),
+            message: String::from("the game does not include me"),
+        }),
+    };
+    let mut draft = LiveDraft {
+        my_champion: None,
+        allies: Vec::new(),
+        enemies: Vec::new(),
+    };
+    for participant in &game.participants {
+        // Champion id 0 or below is a champion not picked yet
+        if participant.champion_id <= 0 {
+            continue;
+        }
+        let champion_name = match find_champion_name(participant.champion_id) {
+            Some(champion_name) => champion_name,
+            None => {
+                warn!(champion_id = participant.champion_id, "Leaving out unknown champion");
+                continue;
+            }
+        };
+        if participant.puuid == puuid {
+            draft.my_champion = Some(champion_name);
+        } else if participant.team_id == my_team_id {
+            draft.allies.push(champion_name);
+        } else {
+            draft.enemies.push(champion_name);
+        }
+    }
+    return Ok(Some(draft));
+}
@@ -1,27 +1,79 @@
-use std::env;
+// Explicit `return` statements are the house style of this crate.
+#![allow(clippy::needless_return)]
 
-mod store;
-mod analyze;
-mod string;
-mod win_rate_info;
-mod champion_info;
+use clap::StructOpt;
 
+mod cli;
+
+use cli::{analyze, champ_select, duo, export, live, options, pool, rank, report, serve, store, tilt, tui};
+
+/// Statistics of League of Legends ranked matches
+#[derive(clap::Parser)]
+#[clap(name = "league_think")]
+struct CommandLineArguments {
+    #[clap(flatten)]
+    options: options::GlobalOptions,
+
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(clap::Subcommand)]
+enum Command {
+    /// Download the ranked match history into the data directory
+    Store,
+    /// Print champion statistics or score a draft
+    Analyze(analyze::CommandLineArguments),
+    /// Write matchup and participant tables as CSV files
+    Export(export::CommandLineArguments),
+    /// Render a self-contained HTML report
+    Report(report::CommandLineArguments),
+    /// Browse champion statistics in a terminal interface
+    Tui,
+    /// Win rates of two players on the same team by champion and role pair
+    Duo(duo::CommandLineArguments),
+    /// Recommend a small champion pool per role with good matchups against the usual enemies
+    Pool,
+    /// Win rates by play session, losing streak and time, with advice on when to stop queuing
+    Tilt,
+    /// LP progression and champion win rates by the tier I was at, from the ranks saved by store
+    Rank,
+    /// Score my champions against the game I am playing right now
+    Live(live::CommandLineArguments),
+    /// Follow the champion select of the running League client and recommend picks
+    ChampSelect(champ_select::CommandLineArguments),
+    /// Answer champion statistics and draft recommendations over a local HTTP JSON API
+    Serve(serve::CommandLineArguments),
+}
+
+fn run(args: CommandLineArguments) -> league_think::error::Result<()> {
+    let settings = cli::config::Settings::load(&args.options)?;
+    settings.log();
+    return match args.command {
+        Command::Store => store::store(&settings),
+        Command::Analyze(command_args) => analyze::analyze(&settings, command_args),
+        Command::Export(command_args) => export::export(&settings, command_args),
+        Command::Report(command_args) => report::report(&settings, command_args),
+        Command::Tui => tui::tui(&settings),
+        Command::Duo(command_args) => duo::duo(&settings, command_args),
+        Command::Pool => pool::pool(&settings),
+        Command::Tilt => tilt::tilt(&settings),
+        Command::Rank => rank::rank(&settings),
+        Command::Live(command_args) => live::live(&settings, command_args),
+        Command::ChampSelect(command_args) => champ_select::champ_select(&settings, command_args),
+        Command::Serve(command_args) => serve::serve(&settings, command_args),
+    };
+}
+
+/// Failures are printed without a backtrace and mapped to an exit code per kind of failure
 pub fn main() {
-    println!("STARTING...");
-    let args: Vec<String> = env::args().collect();
-    let command = args.get(1);
-    match command {
-        Some(command) => {
-            if command == "store" {
-                store::store();
-            } else if command == "analyze" {
-                analyze::analyze();
-            } else {
-                println!("Unknown command: {}", command);
-            }
-        }
-        _ => {
-            println!("Please provide a command");
-        }
+    let args = CommandLineArguments::parse();
+    cli::logging::init(args.options.verbose, args.options.quiet);
+    if args.options.no_color {
+        league_think::string::set_color_enabled(false);
+    }
+    if let Err(error) = run(args) {
+        tracing::error!("{}", error);
+        std::process::exit(error.get_exit_code());
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,149 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+use crate::error::{Error, Result};
+use crate::match_facts::MatchFacts;
+
+/// Stored in the data directory next to the match files
+const CACHE_FILE_NAME: &str = "analysis-cache.bin";
+/// Increased whenever `MatchFacts` changes, which drops every cached entry
+const CACHE_VERSION: u32 = 8;
+
+#[derive(Serialize, Deserialize)]
+struct CacheEntry {
+    /// Modification time of the match file in nanoseconds since the epoch
+    modified: u128,
+    facts: MatchFacts,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CacheFile {
+    version: u32,
+    entries: HashMap<String, CacheEntry>,
+}
+
+/// Facts of already analyzed match files, keyed by file name and modification time
+pub struct MatchCache {
+    file_path: PathBuf,
+    content: CacheFile,
+    is_changed: bool,
+}
+
+impl MatchCache {
+    /// A missing, outdated or unreadable cache file starts an empty cache
+    pub fn load(data_directory: &str) -> MatchCache {
+        let file_path = Path::new(data_directory).join(CACHE_FILE_NAME);
+        // The version comes first, so that an outdated cache is dropped without reading the rest
+        let content = std::fs::read(&file_path).ok()
+            .filter(|bytes| bincode::deserialize::<u32>(bytes).ok() == Some(CACHE_VERSION))
+            .and_then(|bytes| match bincode::deserialize::<CacheFile>(&bytes) {
+                Ok(content) => Some(content),
+                Err(error) => {
+                    warn!(file = %file_path.display(), %error, "Ignoring unreadable cache");
+                    None
+                }
+            })
+            .unwrap_or_else(|| CacheFile {
+                version: CACHE_VERSION,
+                entries: HashMap::new(),
+            });
+        debug!(file = %file_path.display(), entries = content.entries.len(), "Loaded cache");
+        return MatchCache {
+            file_path,
+            content,
+            is_changed: false,
+        };
+    }
+
+    pub fn get(&self, file_name: &str, modified: u128) -> Option<&MatchFacts> {
+        return self.content.entries.get(file_name)
+            .filter(|entry| entry.modified == modified)
+            .map(|entry| &entry.facts);
+    }
+
+    pub fn insert(&mut self, file_name: String, modified: u128, facts: MatchFacts) {
+        self.content.entries.insert(file_name, CacheEntry { modified, facts });
+        self.is_changed = true;
+    }
+
+    /// Writes the cache back if anything was added
+    pub fn save(&self) -> Result<()> {
+        if !self.is_changed {
+            return Ok(());
+        }
+        let bytes = bincode::serialize(&self.content).expect("Serialize cache");
+        return std::fs::write(&self.file_path, bytes)
+            .map_err(|source| Error::Io { file_path: self.file_path.display().to_string(), source });
+    }
+}
+
+/// Modification time of a match file as used by the cache, 0 if the platform does not provide it
+pub fn get_modified(file_path: &std::fs::DirEntry) -> u128 {
+    return file_path.metadata()
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|modified| modified.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|duration| duration.as_nanos())
+        .unwrap_or(0);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// An empty data directory in the temporary directory, unique to the test
+    fn create_data_directory(name: &str) -> String {
+        let directory = std::env::temp_dir()
+            .join(format!("league_think_cache_{}_{}", std::process::id(), name));
+        let _ = std::fs::remove_dir_all(&directory);
+        std::fs::create_dir_all(&directory).unwrap();
+        return directory.display().to_string();
+    }
+
+    fn create_facts(game_creation: i64) -> MatchFacts {
+        return MatchFacts {
+            game_creation,
+            game_duration: 1800,
+            abnormality: None,
+            participants: Vec::new(),
+        };
+    }
+
+    #[test]
+    fn saved_entries_are_reused_by_modification_time() {
+        let data_directory = create_data_directory("reuse");
+        let mut cache = MatchCache::load(&data_directory);
+        assert!(cache.get("EUW1_1.json", 10).is_none());
+        cache.insert(String::from("EUW1_1.json"), 10, create_facts(1650000000000));
+        cache.save().unwrap();
+
+        let cache = MatchCache::load(&data_directory);
+        assert_eq!(cache.get("EUW1_1.json", 10).map(|facts| facts.game_creation), Some(1650000000000));
+        // A match file written again since it was cached is parsed again
+        assert!(cache.get("EUW1_1.json", 11).is_none());
+        assert!(cache.get("EUW1_2.json", 10).is_none());
+    }
+
+    #[test]
+    fn outdated_cache_is_dropped() {
+        let data_directory = create_data_directory("outdated");
+        let mut entries = HashMap::new();
+        entries.insert(String::from("EUW1_1.json"), CacheEntry { modified: 10, facts: create_facts(1650000000000) });
+        let content = CacheFile { version: CACHE_VERSION - 1, entries };
+        let file_path = Path::new(&data_directory).join(CACHE_FILE_NAME);
+        std::fs::write(&file_path, bincode::serialize(&content).unwrap()).unwrap();
+        assert!(MatchCache::load(&data_directory).get("EUW1_1.json", 10).is_none());
+    }
+
+    #[test]
+    fn unreadable_cache_is_dropped() {
+        let data_directory = create_data_directory("unreadable");
+        let file_path = Path::new(&data_directory).join(CACHE_FILE_NAME);
+        let mut bytes = bincode::serialize(&CACHE_VERSION).unwrap();
+        bytes.extend_from_slice(&[0xFF; 3]);
+        std::fs::write(&file_path, bytes).unwrap();
+        let cache = MatchCache::load(&data_directory);
+        assert!(cache.content.entries.is_empty());
+    }
+}
@@ -0,0 +1,291 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use crate::match_model::{Match, Participant};
+
+/// Remakes end before this many seconds, while the earliest surrender vote starts at 15 minutes
+const REMAKE_DURATION_LIMIT: i64 = 300;
+/// Players who earned less gold than this were not really there; everyone earns about 1000 in the first 5 minutes
+const AFK_GOLD_LIMIT: i32 = 1000;
+/// Players who played less than this share of the game left it early
+const AFK_TIME_PLAYED_SHARE: f32 = 0.5;
+
+/// Milliseconds since the epoch as a moment, keeping the milliseconds
+pub fn get_moment_of_timestamp(timestamp: i64) -> NaiveDateTime {
+    return NaiveDateTime::from_timestamp(timestamp / 1000, (timestamp % 1000 * 1_000_000) as u32);
+}
+
+/// Why a match does not count as a normal game
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
+pub enum Abnormality {
+    Remake,
+    /// Some participant was away from keyboard or left early
+    Afk,
+    /// Ended by a surrender vote before 20 minutes
+    EarlySurrender,
+}
+
+impl Abnormality {
+    /// A remake also counts as an early surrender, so it is detected first.
+    /// The game duration is in seconds; 0 is unknown and does not make a remake
+    pub fn detect(match_history: &Match, game_duration: i64) -> Option<Abnormality> {
+        let info = &match_history.info;
+        if game_duration > 0 && game_duration < REMAKE_DURATION_LIMIT {
+            return Some(Abnormality::Remake);
+        }
+        if info.participants.iter().any(|participant| is_afk(participant, game_duration)) {
+            return Some(Abnormality::Afk);
+        }
+        if info.participants.iter().any(|participant| participant.game_ended_in_early_surrender) {
+            return Some(Abnormality::EarlySurrender);
+        }
+        return None;
+    }
+
+    /// Used in the JSON output
+    pub fn get_key(&self) -> &'static str {
+        return match self {
+            Abnormality::Remake => "remake",
+            Abnormality::Afk => "afk",
+            Abnormality::EarlySurrender => "early_surrender",
+        };
+    }
+
+    pub fn get_name(&self) -> &'static str {
+        return match self {
+            Abnormality::Remake => "remakes",
+            Abnormality::Afk => "AFK games",
+            Abnormality::EarlySurrender => "early surrenders",
+        };
+    }
+}
+
+/// Gold and time played of zero mean that an older match file lacks the value, not that the player was away
+fn is_afk(participant: &Participant, game_duration: i64) -> bool {
+    let left_early = participant.time_played > 0
+        && (participant.time_played as f32) < (game_duration as f32) * AFK_TIME_PLAYED_SHARE;
+    let earned_nothing = participant.gold_earned > 0 && participant.gold_earned < AFK_GOLD_LIMIT;
+    return left_early || earned_nothing;
+}
+
+/// What the analysis needs to know about one participant of a match
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ParticipantFacts {
+    pub summoner_id: String,
+    pub puuid: String,
+    /// Riot ID or summoner name
+    pub name: String,
+    pub summoner_name: String,
+    pub champion_name: String,
+    /// Team position such as "TOP"
+    pub role: String,
+    /// 100 for the blue side, 200 for the red side
+    pub team_id: u16,
+    pub win: bool,
+    pub physical_damage: i64,
+    pub magic_damage: i64,
+}
+
+impl ParticipantFacts {
+    pub fn new(participant: &Participant) -> ParticipantFacts {
+        return ParticipantFacts {
+            summoner_id: participant.summoner_id.clone(),
+            puuid: participant.puuid.clone(),
+            name: participant.get_display_name(),
+            summoner_name: participant.summoner_name.clone(),
+            champion_name: participant.champion_name.clone(),
+            role: participant.team_position.clone(),
+            team_id: participant.team_id,
+            win: participant.win,
+            physical_damage: participant.physical_damage_dealt_to_champions,
+            magic_damage: participant.magic_damage_dealt_to_champions,
+        };
+    }
+
+    /// A player is given by summoner id, puuid, Riot ID with or without the tagline or summoner name,
+    /// names ignore case
+    pub fn is_player(&self, player: &str) -> bool {
+        let game_name = self.name.split('#').next().unwrap_or_default();
+        return self.summoner_id == player ||
+            self.puuid == player ||
+            self.name.eq_ignore_ascii_case(player) ||
+            game_name.eq_ignore_ascii_case(player) ||
+            self.summoner_name.eq_ignore_ascii_case(player);
+    }
+}
+
+/// How one summoner took part in a match
+pub struct Participation<'a> {
+    pub me: &'a ParticipantFacts,
+    /// My teammates, without me
+    pub allies: Vec<&'a ParticipantFacts>,
+    pub enemies: Vec<&'a ParticipantFacts>,
+}
+
+/// The facts of one stored match that the analysis depends on, small enough to be cached
+#[derive(Serialize, Deserialize, Clone)]
+pub struct MatchFacts {
+    /// Milliseconds since the epoch
+    pub game_creation: i64,
+    /// Seconds, 0 when unknown
+    pub game_duration: i64,
+    /// `None` for a normal game
+    pub abnormality: Option<Abnormality>,
+    pub participants: Vec<ParticipantFacts>,
+}
+
+impl MatchFacts {
+    pub fn new(match_history: &Match) -> MatchFacts {
+        let info = &match_history.info;
+        // Match files from before patch 11.20 have no end timestamp and give the duration in milliseconds
+        let game_duration = match info.game_end_timestamp {
+            Some(_) => info.game_duration,
+            None => info.game_duration / 1000,
+        };
+        return MatchFacts {
+            game_creation: info.game_creation,
+            game_duration,
+            abnormality: Abnormality::detect(match_history, game_duration),
+            participants: info.participants.iter().map(ParticipantFacts::new).collect(),
+        };
+    }
+
+    /// `None` when the summoner did not play in the match
+    pub fn get_participation(&self, summoner_id: &str) -> Option<Participation<'_>> {
+        let me = self.participants.iter().find(|participant| participant.summoner_id == summoner_id)?;
+        return Some(Participation {
+            me,
+            allies: self.participants.iter()
+                .filter(|participant| participant.team_id == me.team_id && participant.summoner_id != summoner_id)
+                .collect(),
+            enemies: self.participants.iter()
+                .filter(|participant| participant.team_id != me.team_id)
+                .collect(),
+        });
+    }
+
+    pub fn get_moment(&self) -> NaiveDateTime {
+        return get_moment_of_timestamp(self.game_creation);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A 30 minute match of ten participants who all played the whole game
+    fn create_match_json() -> serde_json::Value {
+        let participants: Vec<serde_json::Value> = (0..10)
+            .map(|index| serde_json::json!({
+                "championName": "Ahri",
+                "teamId": if index < 5 { 100 } else { 200 },
+                "win": index < 5,
+                "goldEarned": 12000,
+                "timePlayed": 1800,
+                "gameEndedInEarlySurrender": false,
+            }))
+            .collect();
+        return serde_json::json!({
+            "metadata": { "matchId": "EUW1_1" },
+            "info": {
+                "gameCreation": 1650000000000_i64,
+                "gameDuration": 1800,
+                "gameEndTimestamp": 1650001830000_i64,
+                "participants": participants,
+            },
+        });
+    }
+
+    fn create_facts(match_json: serde_json::Value) -> MatchFacts {
+        let match_history: Match = serde_json::from_value(match_json).unwrap();
+        return MatchFacts::new(&match_history);
+    }
+
+    fn detect(match_json: serde_json::Value) -> Option<Abnormality> {
+        return create_facts(match_json).abnormality;
+    }
+
+    #[test]
+    fn normal_game() {
+        assert_eq!(detect(create_match_json()), None);
+    }
+
+    #[test]
+    fn remake() {
+        let mut match_json = create_match_json();
+        match_json["info"]["gameDuration"] = serde_json::json!(200);
+        match_json["info"]["participants"][0]["gameEndedInEarlySurrender"] = serde_json::json!(true);
+        assert_eq!(detect(match_json), Some(Abnormality::Remake));
+    }
+
+    #[test]
+    fn unknown_game_duration_is_no_remake() {
+        let mut match_json = create_match_json();
+        match_json["info"]["gameDuration"] = serde_json::json!(0);
+        assert_eq!(detect(match_json.clone()), None);
+        match_json["info"].as_object_mut().unwrap().remove("gameDuration");
+        assert_eq!(detect(match_json), None);
+    }
+
+    #[test]
+    fn old_game_duration_in_milliseconds() {
+        let mut match_json = create_match_json();
+        match_json["info"].as_object_mut().unwrap().remove("gameEndTimestamp");
+        match_json["info"]["gameDuration"] = serde_json::json!(1800000);
+        let facts = create_facts(match_json.clone());
+        assert_eq!(facts.game_duration, 1800);
+        assert_eq!(facts.abnormality, None);
+        match_json["info"]["gameDuration"] = serde_json::json!(200000);
+        assert_eq!(detect(match_json), Some(Abnormality::Remake));
+    }
+
+    #[test]
+    fn moment_keeps_milliseconds() {
+        let moment = get_moment_of_timestamp(1650000000123);
+        assert_eq!(moment.to_string(), "2022-04-15 05:20:00.123");
+    }
+
+    #[test]
+    fn player_by_riot_id_with_or_without_tagline() {
+        let mut match_json = create_match_json();
+        match_json["info"]["participants"][0]["riotIdGameName"] = serde_json::json!("Yuma");
+        match_json["info"]["participants"][0]["riotIdTagline"] = serde_json::json!("EUW");
+        let facts = create_facts(match_json);
+        let participant = &facts.participants[0];
+        assert!(participant.is_player("yuma#euw"));
+        assert!(participant.is_player("Yuma"));
+        assert!(!participant.is_player("Yum"));
+    }
+
+    #[test]
+    fn afk_by_gold() {
+        let mut match_json = create_match_json();
+        match_json["info"]["participants"][7]["goldEarned"] = serde_json::json!(500);
+        assert_eq!(detect(match_json), Some(Abnormality::Afk));
+    }
+
+    #[test]
+    fn afk_by_time_played() {
+        let mut match_json = create_match_json();
+        match_json["info"]["participants"][3]["timePlayed"] = serde_json::json!(600);
+        assert_eq!(detect(match_json), Some(Abnormality::Afk));
+    }
+
+    #[test]
+    fn missing_gold_and_time_played_is_no_afk() {
+        let mut match_json = create_match_json();
+        match_json["info"]["participants"][3]["goldEarned"] = serde_json::json!(0);
+        match_json["info"]["participants"][4]["timePlayed"] = serde_json::json!(0);
+        assert_eq!(detect(match_json), None);
+    }
+
+    #[test]
+    fn early_surrender() {
+        let mut match_json = create_match_json();
+        match_json["info"]["gameDuration"] = serde_json::json!(960);
+        for index in 0..10 {
+            match_json["info"]["participants"][index]["timePlayed"] = serde_json::json!(960);
+            match_json["info"]["participants"][index]["gameEndedInEarlySurrender"] = serde_json::json!(true);
+        }
+        assert_eq!(detect(match_json), Some(Abnormality::EarlySurrender));
+    }
+}
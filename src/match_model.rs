@@ -0,0 +1,94 @@
+//! The part of a stored match-v5 JSON file that league_think reads.
+//!
+//! Unknown fields are ignored and fields which are not needed for the win rates have defaults,
+//! so match files of newer API versions load even where the `riven` match types no longer fit them.
+
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Match {
+    pub metadata: Metadata,
+    pub info: Info,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Metadata {
+    pub match_id: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Info {
+    /// Milliseconds since the epoch
+    pub game_creation: i64,
+    /// Seconds, but milliseconds in match files without `game_end_timestamp`; 0 when the match file lacks it
+    #[serde(default)]
+    pub game_duration: i64,
+    /// Milliseconds since the epoch, missing in match files from before patch 11.20
+    #[serde(default)]
+    pub game_end_timestamp: Option<i64>,
+    #[serde(default)]
+    pub queue_id: u16,
+    pub participants: Vec<Participant>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Participant {
+    #[serde(default)]
+    pub summoner_id: String,
+    #[serde(default)]
+    pub puuid: String,
+    #[serde(default)]
+    pub summoner_name: String,
+    /// Riot ID name, which replaced the summoner name
+    #[serde(default)]
+    pub riot_id_game_name: Option<String>,
+    /// Riot ID name as named by older match files
+    #[serde(default)]
+    pub riot_id_name: Option<String>,
+    #[serde(default)]
+    pub riot_id_tagline: Option<String>,
+    pub champion_name: String,
+    pub team_id: u16,
+    #[serde(default)]
+    pub team_position: String,
+    pub win: bool,
+    #[serde(default)]
+    pub kills: i32,
+    #[serde(default)]
+    pub deaths: i32,
+    #[serde(default)]
+    pub assists: i32,
+    #[serde(default)]
+    pub gold_earned: i32,
+    #[serde(default)]
+    pub total_minions_killed: i32,
+    #[serde(default)]
+    pub physical_damage_dealt_to_champions: i64,
+    #[serde(default)]
+    pub magic_damage_dealt_to_champions: i64,
+    #[serde(default)]
+    pub true_damage_dealt_to_champions: i64,
+    /// Seconds
+    #[serde(default)]
+    pub time_played: i32,
+    #[serde(default)]
+    pub game_ended_in_early_surrender: bool,
+}
+
+impl Participant {
+    /// Riot ID as "name#tagline" when the match has it, the summoner name otherwise
+    pub fn get_display_name(&self) -> String {
+        let name = self.riot_id_game_name.as_ref()
+            .filter(|name| !name.is_empty())
+            .or(self.riot_id_name.as_ref());
+        return match (name, &self.riot_id_tagline) {
+            (Some(name), Some(tagline)) if !name.is_empty() && !tagline.is_empty() => format!("{}#{}", name, tagline),
+            (Some(name), _) if !name.is_empty() => name.clone(),
+            _ => self.summoner_name.clone(),
+        };
+    }
+}
@@ -0,0 +1,69 @@
+use std::ops::Add;
+use crate::string::INDENTATION_STRING;
+use crate::win_rate_info::WinRateInfo;
+
+/// Players met at least this often are listed in the player summary
+pub const RECURRING_PLAYER_THRESHOLD: i32 = 2;
+/// Length of the teammate and opponent lists in the player summary
+const PLAYER_LIST_LIMIT: usize = 20;
+
+/// My record together with and against one other player, who is identified by the puuid
+pub struct PlayerInfo {
+    /// Riot ID or summoner name of the latest match with this player
+    pub name: String,
+    win_rate_with: WinRateInfo,
+    win_rate_vs: WinRateInfo,
+}
+
+impl PlayerInfo {
+    pub fn new(name: String, significance_threshold: i32) -> PlayerInfo {
+        return PlayerInfo {
+            name,
+            win_rate_with: WinRateInfo::with_significance_threshold(significance_threshold),
+            win_rate_vs: WinRateInfo::with_significance_threshold(significance_threshold),
+        };
+    }
+
+    pub fn get_win_rate_with(&mut self) -> &mut WinRateInfo {
+        return &mut self.win_rate_with;
+    }
+
+    pub fn get_win_rate_vs(&mut self) -> &mut WinRateInfo {
+        return &mut self.win_rate_vs;
+    }
+
+    pub fn get_record_with(&self) -> &WinRateInfo {
+        return &self.win_rate_with;
+    }
+
+    pub fn get_record_vs(&self) -> &WinRateInfo {
+        return &self.win_rate_vs;
+    }
+
+    /// Players met at least `RECURRING_PLAYER_THRESHOLD` times, most often met first
+    pub fn get_recurring_list<'a>(player_infos: impl Iterator<Item = &'a PlayerInfo>,
+            get_record: fn(&PlayerInfo) -> &WinRateInfo) -> Vec<(&'a str, &'a WinRateInfo)> {
+        let mut players: Vec<(&str, &WinRateInfo)> = player_infos
+            .map(|player_info| (player_info.name.as_str(), get_record(player_info)))
+            .filter(|(_, win_rate_info)| win_rate_info.get_count_of_matches() >= RECURRING_PLAYER_THRESHOLD)
+            .collect();
+        players.sort_by(|a, b| b.1.get_count_of_matches().cmp(&a.1.get_count_of_matches())
+            .then_with(|| a.0.cmp(b.0)));
+        return players;
+    }
+
+    pub fn get_summary_text<'a>(player_infos: impl Iterator<Item = &'a PlayerInfo> + Clone) -> String {
+        let mut text = String::new();
+        for (title, get_record) in [
+            ("teammates", PlayerInfo::get_record_with as fn(&PlayerInfo) -> &WinRateInfo),
+            ("recurring opponents", PlayerInfo::get_record_vs),
+        ] {
+            let players = PlayerInfo::get_recurring_list(player_infos.clone(), get_record);
+            let shown_players: Vec<(&str, &WinRateInfo)> = players.iter().take(PLAYER_LIST_LIMIT).copied().collect();
+            text = text.add(title).add(": ").add(&players.len().to_string());
+            text.push('\n');
+            text = text.add(&WinRateInfo::format_list_of_named(&shown_players, INDENTATION_STRING));
+        }
+        return text;
+    }
+}
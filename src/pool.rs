@@ -0,0 +1,152 @@
+use std::collections::HashMap;
+use std::ops::Add;
+use crate::champion_info::ChampionInfo;
+use crate::string::{format_percent, INDENTATION_STRING};
+
+const MIN_POOL_SIZE: usize = 2;
+const MAX_POOL_SIZE: usize = 4;
+/// A pool beyond the minimum size only grows by champions adding at least this share of the coverage so far
+const MIN_COVERAGE_GAIN: f32 = 0.1;
+/// Enemies most frequently met in a role which are considered for the coverage
+const ENEMY_LIMIT: usize = 20;
+/// Length of the list of uncovered enemies in the summary
+const UNCOVERED_LIMIT: usize = 5;
+
+/// Which champions I played and met in one role
+pub struct RoleInfo {
+    pub count_of_matches: i32,
+    champion_counts: HashMap<String, i32>,
+    enemy_counts: HashMap<String, i32>,
+}
+
+impl RoleInfo {
+    pub fn new() -> RoleInfo {
+        return RoleInfo {
+            count_of_matches: 0,
+            champion_counts: HashMap::new(),
+            enemy_counts: HashMap::new(),
+        };
+    }
+
+    pub fn add(&mut self, my_champion: &str, enemies: impl Iterator<Item = String>) {
+        self.count_of_matches += 1;
+        *self.champion_counts.entry(String::from(my_champion)).or_default() += 1;
+        for enemy in enemies {
+            *self.enemy_counts.entry(enemy).or_default() += 1;
+        }
+    }
+
+    pub fn get_champion_counts(&self) -> &HashMap<String, i32> {
+        return &self.champion_counts;
+    }
+
+    pub fn get_enemy_counts(&self) -> &HashMap<String, i32> {
+        return &self.enemy_counts;
+    }
+}
+
+impl Default for RoleInfo {
+    fn default() -> RoleInfo {
+        return RoleInfo::new();
+    }
+}
+
+/// A small set of champions of one role which together have good matchups against the usual enemies
+pub struct PoolRecommendation {
+    pub role: String,
+    pub count_of_matches: i32,
+    pub champions: Vec<String>,
+    /// Share of the enemy picks, by frequency, which at least one pool champion wins more often than not
+    pub coverage: f32,
+    /// Frequent enemies no pool champion has a good matchup against, with their count of picks
+    pub uncovered_enemies: Vec<(String, i32)>,
+}
+
+impl PoolRecommendation {
+    /// Greedily adds the champion which covers the most of the remaining enemy picks.
+    ///
+    /// A matchup counts with its win chance above 50%, so enemies I often met weigh more
+    /// and matchups of a few games count less than the same win rate over many games.
+    pub fn new(role: &str, role_info: &RoleInfo, champion_infos: &HashMap<String, ChampionInfo>) -> PoolRecommendation {
+        let mut enemies: Vec<(&String, &i32)> = role_info.enemy_counts.iter().collect();
+        enemies.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+        enemies.truncate(ENEMY_LIMIT);
+        let total_count: i32 = enemies.iter().map(|(_, count)| **count).sum();
+
+        let mut candidates: Vec<&String> = role_info.champion_counts.keys().collect();
+        candidates.sort();
+        let mut champions: Vec<String> = Vec::new();
+        let mut best_advantages: Vec<f32> = vec![0.0; enemies.len()];
+        let mut coverage_score = 0.0;
+        while champions.len() < MAX_POOL_SIZE {
+            let best_candidate = candidates.iter()
+                .filter(|candidate| !champions.contains(candidate))
+                .filter_map(|candidate| champion_infos.get(*candidate).map(|info| (*candidate, info)))
+                .map(|(candidate, champion_info)| {
+                    let gain: f32 = enemies.iter().zip(&best_advantages)
+                        .map(|((enemy, count), best_advantage)| {
+                            let advantage = get_advantage(champion_info, enemy);
+                            return (advantage - best_advantage).max(0.0) * (**count as f32);
+                        })
+                        .sum();
+                    return (candidate, champion_info, gain);
+                })
+                .max_by(|a, b| a.2.partial_cmp(&b.2).unwrap().then_with(|| b.0.cmp(a.0)));
+            let (candidate, champion_info, gain) = match best_candidate {
+                Some(best_candidate) => best_candidate,
+                None => break,
+            };
+            if champions.len() >= MIN_POOL_SIZE && gain < coverage_score * MIN_COVERAGE_GAIN {
+                break;
+            }
+            for ((enemy, _), best_advantage) in enemies.iter().zip(best_advantages.iter_mut()) {
+                *best_advantage = best_advantage.max(get_advantage(champion_info, enemy));
+            }
+            coverage_score += gain;
+            champions.push(candidate.clone());
+        }
+
+        let mut covered_count = 0;
+        let mut uncovered_enemies = Vec::new();
+        for ((enemy, count), best_advantage) in enemies.iter().zip(&best_advantages) {
+            if *best_advantage > 0.0 {
+                covered_count += **count;
+            } else {
+                uncovered_enemies.push(((*enemy).clone(), **count));
+            }
+        }
+        return PoolRecommendation {
+            role: String::from(role),
+            count_of_matches: role_info.count_of_matches,
+            champions,
+            coverage: if total_count > 0 { covered_count as f32 / total_count as f32 } else { 0.0 },
+            uncovered_enemies,
+        };
+    }
+
+    pub fn get_summary_text(&self) -> String {
+        let mut text = String::new();
+        text = text.add(&self.role).add(" (").add(&self.count_of_matches.to_string()).add(" matches): ")
+            .add(&self.champions.join(", "));
+        text.push('\n');
+        text = text.add(INDENTATION_STRING).add("covers ").add(&format_percent(self.coverage))
+            .add(" of the frequent enemy picks");
+        text.push('\n');
+        if !self.uncovered_enemies.is_empty() {
+            let uncovered: Vec<String> = self.uncovered_enemies.iter()
+                .take(UNCOVERED_LIMIT)
+                .map(|(enemy, count)| format!("{} {}", enemy, count))
+                .collect();
+            text = text.add(INDENTATION_STRING).add("uncovered: ").add(&uncovered.join(", "));
+            text.push('\n');
+        }
+        return text;
+    }
+}
+
+/// How much better than even my champion does against the enemy, 0 for an even, bad or unknown matchup
+fn get_advantage(champion_info: &ChampionInfo, enemy: &str) -> f32 {
+    return champion_info.get_win_rates_vs_champions().get(enemy)
+        .map(|win_rate_info| (win_rate_info.get_win_chance() - 0.5).max(0.0))
+        .unwrap_or(0.0);
+}
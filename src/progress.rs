@@ -0,0 +1,16 @@
+use indicatif::{ProgressBar, ProgressDrawTarget, ProgressStyle};
+
+/// Progress bar on stderr, so that stdout only carries results.
+/// A hidden progress bar is still safe to update.
+pub fn create_progress_bar(length: usize, message: &str, visible: bool) -> ProgressBar {
+    let progress_bar = ProgressBar::with_draw_target(Some(length as u64), if visible {
+        ProgressDrawTarget::stderr()
+    } else {
+        ProgressDrawTarget::hidden()
+    });
+    progress_bar.set_style(ProgressStyle::with_template("{msg} [{bar:40}] {pos}/{len} ({eta})")
+        .expect("Valid progress bar template")
+        .progress_chars("=> "));
+    progress_bar.set_message(String::from(message));
+    return progress_bar;
+}
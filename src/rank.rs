@@ -0,0 +1,168 @@
+//! Ranked tier history of the tracked summoner.
+//!
+//! Every `store` run appends the current solo queue rank as one JSON line to `rank-history.jsonl`
+//! in the data directory. A match is played at the rank of the latest snapshot before it.
+
+use std::collections::HashMap;
+use std::io::Write;
+use std::ops::Add;
+use std::path::Path;
+use chrono::NaiveDateTime;
+use riven::consts::Division;
+use serde::{Deserialize, Serialize};
+use crate::analyze::MatchResult;
+use crate::error::{Error, Result};
+use crate::match_facts::get_moment_of_timestamp;
+use crate::string::INDENTATION_STRING;
+use crate::win_rate_info::WinRateInfo;
+
+pub const RANK_HISTORY_FILE_NAME: &str = "rank-history.jsonl";
+
+/// Tiers below the apex tiers, lowest first; each has four divisions of 100 LP
+const STANDARD_TIERS: [&str; 7] = ["IRON", "BRONZE", "SILVER", "GOLD", "PLATINUM", "EMERALD", "DIAMOND"];
+/// Tiers without divisions, lowest first
+const APEX_TIERS: [&str; 3] = ["MASTER", "GRANDMASTER", "CHALLENGER"];
+const LEAGUE_POINTS_PER_DIVISION: i32 = 100;
+const DIVISIONS_PER_TIER: i32 = 4;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct RankSnapshot {
+    /// Milliseconds since the epoch
+    pub timestamp: i64,
+    /// Such as "GOLD", kept as text so that tiers added after this version are still saved
+    pub tier: String,
+    pub division: Division,
+    pub league_points: i32,
+    pub wins: i32,
+    pub losses: i32,
+}
+
+impl RankSnapshot {
+    pub fn get_moment(&self) -> NaiveDateTime {
+        return get_moment_of_timestamp(self.timestamp);
+    }
+
+    /// LP counted from Iron IV 0 LP, so that snapshots of different tiers can be compared
+    pub fn get_ladder_points(&self) -> i32 {
+        let tier_points = LEAGUE_POINTS_PER_DIVISION * DIVISIONS_PER_TIER;
+        if self.is_apex() {
+            return STANDARD_TIERS.len() as i32 * tier_points + self.league_points;
+        }
+        // Unknown tiers count as the lowest one
+        let tier_index = STANDARD_TIERS.iter().position(|tier| *tier == self.tier).unwrap_or(0) as i32;
+        let division_index = DIVISIONS_PER_TIER - u8::from(self.division) as i32;
+        return tier_index * tier_points + division_index * LEAGUE_POINTS_PER_DIVISION + self.league_points;
+    }
+
+    pub fn is_apex(&self) -> bool {
+        return APEX_TIERS.contains(&self.tier.as_str());
+    }
+
+    /// Such as "GOLD II 54 LP", apex tiers have no division
+    pub fn get_rank_text(&self) -> String {
+        if self.is_apex() {
+            return format!("{} {} LP", self.tier, self.league_points);
+        } else {
+            return format!("{} {} {} LP", self.tier, self.division, self.league_points);
+        }
+    }
+}
+
+/// Position on the ladder, lowest first; unknown tiers come last
+fn get_tier_order(tier: &str) -> usize {
+    return STANDARD_TIERS.iter().chain(APEX_TIERS.iter())
+        .position(|known_tier| *known_tier == tier)
+        .unwrap_or(STANDARD_TIERS.len() + APEX_TIERS.len());
+}
+
+/// Snapshots oldest first; a missing history file is an empty history
+pub fn load_rank_history(data_directory: &str) -> Result<Vec<RankSnapshot>> {
+    let file_path = Path::new(data_directory).join(RANK_HISTORY_FILE_NAME);
+    if !file_path.exists() {
+        return Ok(Vec::new());
+    }
+    let file_path_text = file_path.display().to_string();
+    let file_content = std::fs::read_to_string(&file_path)
+        .map_err(|source| Error::from_io(&file_path_text, source))?;
+    let mut history = Vec::new();
+    for line in file_content.lines().filter(|line| !line.trim().is_empty()) {
+        let snapshot: RankSnapshot = serde_json::from_str(line)
+            .map_err(|source| Error::InvalidRankHistory { file_path: file_path_text.clone(), source })?;
+        history.push(snapshot);
+    }
+    history.sort_by_key(|snapshot| snapshot.timestamp);
+    return Ok(history);
+}
+
+pub fn append_rank_snapshot(data_directory: &str, snapshot: &RankSnapshot) -> Result<()> {
+    let file_path = Path::new(data_directory).join(RANK_HISTORY_FILE_NAME);
+    let file_path_text = file_path.display().to_string();
+    let mut line = serde_json::to_string(snapshot).expect("Serialize rank snapshot");
+    line.push('\n');
+    return std::fs::OpenOptions::new().create(true).append(true).open(&file_path)
+        .and_then(|mut file| file.write_all(line.as_bytes()))
+        .map_err(|source| Error::from_io(&file_path_text, source));
+}
+
+/// LP progression and my champion win rates by the tier I was at when playing them
+pub struct RankInfo<'a> {
+    history: &'a [RankSnapshot],
+    /// Keyed by tier; matches before the first snapshot have no known tier and are left out
+    win_rates_by_tier: HashMap<String, HashMap<String, WinRateInfo>>,
+}
+
+impl<'a> RankInfo<'a> {
+    /// The history is expected oldest first, as loaded by `load_rank_history`
+    pub fn new(history: &'a [RankSnapshot], match_results: &[MatchResult], significance_threshold: i32) -> RankInfo<'a> {
+        let mut win_rates_by_tier: HashMap<String, HashMap<String, WinRateInfo>> = HashMap::new();
+        for result in match_results {
+            let snapshot = history.iter().rev().find(|snapshot| snapshot.get_moment() <= result.moment);
+            if let Some(snapshot) = snapshot {
+                win_rates_by_tier.entry(snapshot.tier.clone()).or_default()
+                    .entry(result.champion_name.clone())
+                    .or_insert_with(|| WinRateInfo::with_significance_threshold(significance_threshold))
+                    .add(result.win);
+            }
+        }
+        return RankInfo { history, win_rates_by_tier };
+    }
+
+    pub fn get_win_rates_by_tier(&self) -> &HashMap<String, HashMap<String, WinRateInfo>> {
+        return &self.win_rates_by_tier;
+    }
+
+    pub fn get_progression_text(&self) -> String {
+        let mut text = String::new();
+        let mut previous_points: Option<i32> = None;
+        for snapshot in self.history {
+            let points = snapshot.get_ladder_points();
+            text = text.add(INDENTATION_STRING).add(&snapshot.get_moment().format("%Y-%m-%d %H:%M").to_string())
+                .add("  ").add(&snapshot.get_rank_text());
+            if let Some(previous_points) = previous_points {
+                text = text.add(&format!(" ({:+} LP)", points - previous_points));
+            }
+            text = text.add(&format!(", {} wins {} losses", snapshot.wins, snapshot.losses));
+            text.push('\n');
+            previous_points = Some(points);
+        }
+        return text;
+    }
+
+    pub fn get_summary_text(&self) -> String {
+        let mut text = String::from("LP progression: ");
+        text = text.add(&self.history.len().to_string()).add(" snapshots\n");
+        text = text.add(&self.get_progression_text());
+        let mut tiers: Vec<(&String, &HashMap<String, WinRateInfo>)> = self.win_rates_by_tier.iter().collect();
+        tiers.sort_by_key(|(tier, _)| std::cmp::Reverse(get_tier_order(tier)));
+        for (tier, win_rates) in tiers {
+            let mut champions: Vec<(&str, &WinRateInfo)> = win_rates.iter()
+                .map(|(champion_name, info)| (champion_name.as_str(), info))
+                .collect();
+            champions.sort_by(|a, b| b.1.get_count_of_matches().cmp(&a.1.get_count_of_matches())
+                .then_with(|| a.0.cmp(b.0)));
+            text = text.add("champions at ").add(tier).add(":\n");
+            text = text.add(&WinRateInfo::format_list_of_named(&champions, INDENTATION_STRING));
+        }
+        return text;
+    }
+}
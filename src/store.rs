@@ -1,50 +1,128 @@
 use std::path::Path;
-use std::ops::Add;
-use serde_json;
 use riven::RiotApi;
 use riven::RiotApiConfig;
+use serde::Deserialize;
+use crate::error::{Error, Result};
+use crate::progress::create_progress_bar;
+use crate::rank::{append_rank_snapshot, RankSnapshot};
+use tracing::{info, warn};
 
-const REGIONAL_ROUTE: riven::consts::RegionalRoute = riven::consts::RegionalRoute::EUROPE;
 const PAGE_SIZE: i32 = 100;
 
-struct Reader {
+/// The part of a league-v4 entry that the rank history needs.
+/// Parsed here rather than by riven, whose `Tier` does not know every tier the API returns, e.g. EMERALD
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LeagueEntry {
+    queue_type: String,
+    /// Missing during the placement matches
+    tier: Option<String>,
+    rank: Option<riven::consts::Division>,
+    league_points: i32,
+    wins: i32,
+    losses: i32,
+}
+
+/// Downloads the ranked match history of one summoner into the data directory
+pub struct Reader {
     riot_api: riven::RiotApi,
+    data_directory: String,
+    summoner_name: String,
+    platform: riven::consts::PlatformRoute,
     summoner: Option<Box<riven::models::summoner_v4::Summoner>>,
     match_ids: Vec<String>,
+    show_progress: bool,
 }
 
 impl Reader {
-    pub fn new(api_key: &str) -> Reader {
+    pub fn new(api_key: &str, data_directory: String, summoner_name: String,
+            platform: riven::consts::PlatformRoute) -> Reader {
         Reader {
             riot_api: RiotApi::new(RiotApiConfig::with_key(api_key.trim()).preconfig_burst()),
+            data_directory,
+            summoner_name,
+            platform,
             summoner: None,
             match_ids: Vec::new(),
+            show_progress: false,
         }
     }
 
-    pub async fn read(&mut self) {
-        self.read_summoner().await;
-        self.read_match_ids().await;
-        self.read_match_history().await;
+    /// Draw a progress bar on stderr while downloading the match files
+    pub fn set_show_progress(&mut self, show_progress: bool) {
+        self.show_progress = show_progress;
     }
 
-    async fn read_summoner(&mut self) {
+    pub async fn read(&mut self) -> Result<()> {
+        self.read_summoner().await?;
+        // The rank history is optional, so it must not keep the matches from being downloaded
+        if let Err(error) = self.read_rank().await {
+            warn!(%error, "Cannot save rank");
+        }
+        self.read_match_ids().await?;
+        self.read_match_history().await?;
+        return Ok(());
+    }
+
+    async fn read_summoner(&mut self) -> Result<()> {
         let summoner = self.riot_api.summoner_v4()
-            .get_by_summoner_name(riven::consts::PlatformRoute::EUW1, "YumaWhen").await
-            .expect("Read summoner info")
-            .expect("Find an existing summoner");
+            .get_by_summoner_name(self.platform, &self.summoner_name).await
+            .map_err(|source| Error::Api { action: String::from("read summoner info"), source: Box::new(source) })?
+            .ok_or_else(|| Error::SummonerNotFound(self.summoner_name.clone()))?;
         self.summoner = Some(Box::new(summoner));
+        return Ok(());
     }
 
-    async fn read_match_ids(&mut self) {
-        let summoner = self.summoner.as_ref().expect("summoner is required");
+    /// Appends the current solo queue rank to the rank history
+    async fn read_rank(&self) -> Result<()> {
+        let summoner_id = match &self.summoner {
+            Some(summoner) => summoner.id.clone(),
+            None => return Err(Error::SummonerNotFound(self.summoner_name.clone())),
+        };
+        let route: &'static str = self.platform.into();
+        let request = self.riot_api.request(reqwest::Method::GET, route,
+            &format!("/lol/league/v4/entries/by-summoner/{}", summoner_id));
+        let response = self.riot_api.execute_raw("league-v4.getLeagueEntriesForSummoner", route, request).await
+            .map_err(|source| Error::Api { action: String::from("read rank"), source: Box::new(source) })?
+            .response;
+        let request_error = |error: reqwest::Error| Error::Request {
+            action: String::from("read rank"), message: error.to_string() };
+        let entries: Vec<LeagueEntry> = response.error_for_status().map_err(request_error)?
+            .json().await.map_err(request_error)?;
+        // Entries without a tier are still in their placement matches
+        let entry = entries.iter()
+            .find(|entry| entry.queue_type == riven::consts::QueueType::RANKED_SOLO_5x5.as_ref())
+            .and_then(|entry| Some((entry, entry.tier.clone()?, entry.rank?)));
+        match entry {
+            Some((entry, tier, division)) => {
+                let snapshot = RankSnapshot {
+                    timestamp: chrono::Utc::now().timestamp_millis(),
+                    tier,
+                    division,
+                    league_points: entry.league_points,
+                    wins: entry.wins,
+                    losses: entry.losses,
+                };
+                append_rank_snapshot(&self.data_directory, &snapshot)?;
+                info!(rank = %snapshot.get_rank_text(), "Saved rank");
+            },
+            None => info!("Unranked in solo queue"),
+        }
+        return Ok(());
+    }
+
+    async fn read_match_ids(&mut self) -> Result<()> {
+        let puuid = match &self.summoner {
+            Some(summoner) => summoner.puuid.clone(),
+            None => return Err(Error::SummonerNotFound(self.summoner_name.clone())),
+        };
         self.match_ids.clear();
         let mut offset: i32 = 0;
         while offset >= 0 {
             let match_ids = self.riot_api.match_v5()
                 .get_match_ids_by_puuid(
-                    REGIONAL_ROUTE,
-                    summoner.puuid.as_str(),
+                    self.platform.to_regional(),
+                    puuid.as_str(),
                     Some(PAGE_SIZE),
                     None,
                     Some(riven::consts::Queue::SUMMONERS_RIFT_5V5_RANKED_SOLO),
@@ -52,7 +130,7 @@ impl Reader {
                     Some(offset),
                     None
                 ).await
-                .expect("Match ids");
+                .map_err(|source| Error::Api { action: String::from("read match ids"), source: Box::new(source) })?;
             offset = if match_ids.len() >= (PAGE_SIZE as usize) {
                 offset + PAGE_SIZE
             } else {
@@ -60,39 +138,31 @@ impl Reader {
             };
             self.match_ids.extend(match_ids);
         };
-        println!("Match ids found: {}", self.match_ids.len());
+        info!(count = self.match_ids.len(), "Match ids found");
+        return Ok(());
     }
 
-    async fn read_match_history(&self) {
-        for (i, match_id) in self.match_ids.iter().enumerate() {
+    async fn read_match_history(&self) -> Result<()> {
+        let progress_bar = create_progress_bar(self.match_ids.len(), "Saving match history", self.show_progress);
+        for match_id in &self.match_ids {
+            progress_bar.inc(1);
             let file_path = self.get_match_history_file_path(match_id);
-            if !Path::new(&file_path).exists() {
-                println!("Saving match history {} of {}", i, self.match_ids.len());
-                let match_history = self.riot_api.match_v5().get_match(REGIONAL_ROUTE, match_id).await
-                    .expect("Read match history")
-                    .expect("Find match history");
+            if !file_path.exists() {
+                let match_history = self.riot_api.match_v5().get_match(self.platform.to_regional(), match_id).await
+                    .map_err(|source| Error::Api { action: format!("read match {}", match_id), source: Box::new(source) })?
+                    .ok_or_else(|| Error::MatchNotFound(match_id.clone()))?;
                 let match_history_json = serde_json::to_string_pretty(&match_history)
                     .expect("Serialize match history");
-                std::fs::write(file_path, match_history_json)
-                    .expect("Save json to file");
+                std::fs::write(&file_path, match_history_json)
+                    .map_err(|source| Error::from_io(&file_path.display().to_string(), source))?;
             }
         }
-        println!("Saved match history [{}]", self.match_ids.len());
+        progress_bar.finish_and_clear();
+        info!(count = self.match_ids.len(), "Saved match history");
+        return Ok(());
     }
 
-    fn get_match_history_file_path<'a>(&self, id: &str) -> String {
-        String::from("./data/") + id + ".json"
+    fn get_match_history_file_path(&self, id: &str) -> std::path::PathBuf {
+        Path::new(&self.data_directory).join(String::from(id) + ".json")
     }
 }
-
-const RIOT_API_KEY_FILE_PATH: &str = "./riot-api-key.txt";
-
-pub fn store() {
-    let runtime = tokio::runtime::Runtime::new().unwrap();
-    runtime.block_on(async {
-        let api_key = std::fs::read_to_string(RIOT_API_KEY_FILE_PATH)
-            .expect(String::from("Need file: ").add(RIOT_API_KEY_FILE_PATH).as_str());
-        let mut reader = Reader::new(&api_key);
-        reader.read().await;
-    });
-}
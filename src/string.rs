@@ -1,4 +1,13 @@
+use std::io::IsTerminal;
+use std::sync::atomic::{AtomicU8, Ordering};
+
 pub const INDENTATION_STRING: &str = "  ";
+const COLUMN_SEPARATOR: &str = "  ";
+
+const COLOR_UNKNOWN: u8 = 0;
+const COLOR_ENABLED: u8 = 1;
+const COLOR_DISABLED: u8 = 2;
+static COLOR_MODE: AtomicU8 = AtomicU8::new(COLOR_UNKNOWN);
 
 pub fn indent_string(text: &str) -> String {
     let mut result = String::new();
@@ -37,7 +46,157 @@ pub fn format_ratio_detailed(a: i32, b: i32) -> String {
 
 pub fn format_percent(a: f32) -> String {
     let percent = (a * 100.0) as i32;
-    let mut text = String::from(percent.to_string());
+    let mut text = percent.to_string();
     text.push('%');
     return text;
-}
\ No newline at end of file
+}
+
+/// Quotes the field if it contains a separator, a quote or a line break
+pub fn escape_csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        let mut text = String::from("\"");
+        text.push_str(&field.replace('"', "\"\""));
+        text.push('"');
+        return text;
+    } else {
+        return String::from(field);
+    }
+}
+
+pub fn format_csv_row(fields: &[String]) -> String {
+    let fields: Vec<String> = fields.iter().map(|field| escape_csv_field(field)).collect();
+    let mut text = fields.join(",");
+    text.push('\n');
+    return text;
+}
+
+pub fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    return escaped;
+}
+
+/// Overrides the automatic choice, which colors only when stdout is a terminal
+pub fn set_color_enabled(enabled: bool) {
+    COLOR_MODE.store(if enabled { COLOR_ENABLED } else { COLOR_DISABLED }, Ordering::Relaxed);
+}
+
+pub fn is_color_enabled() -> bool {
+    let mode = COLOR_MODE.load(Ordering::Relaxed);
+    if mode == COLOR_UNKNOWN {
+        let enabled = std::io::stdout().is_terminal();
+        set_color_enabled(enabled);
+        return enabled;
+    }
+    return mode == COLOR_ENABLED;
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum TextColor {
+    Green,
+    Red,
+}
+
+impl TextColor {
+    /// Green for a good chance to win, red for a bad one
+    pub fn from_win_chance(chance: f32) -> Option<TextColor> {
+        if chance > 0.5 {
+            return Some(TextColor::Green);
+        } else if chance < 0.5 {
+            return Some(TextColor::Red);
+        } else {
+            return None;
+        }
+    }
+
+    fn get_escape_code(self) -> &'static str {
+        return match self {
+            TextColor::Green => "\x1b[32m",
+            TextColor::Red => "\x1b[31m",
+        };
+    }
+}
+
+pub fn colorize(text: &str, color: Option<TextColor>) -> String {
+    return match color {
+        Some(color) if is_color_enabled() => format!("{}{}\x1b[0m", color.get_escape_code(), text),
+        _ => String::from(text),
+    };
+}
+
+pub struct TableCell {
+    text: String,
+    color: Option<TextColor>,
+}
+
+impl TableCell {
+    pub fn new(text: String) -> TableCell {
+        return TableCell { text, color: None };
+    }
+
+    pub fn colored(text: String, color: Option<TextColor>) -> TableCell {
+        return TableCell { text, color };
+    }
+}
+
+/// Plain text table: the first column is aligned to the left, all others to the right
+pub struct TextTable {
+    rows: Vec<Vec<TableCell>>,
+}
+
+impl TextTable {
+    pub fn new() -> TextTable {
+        return TextTable { rows: Vec::new() };
+    }
+
+    pub fn add_row(&mut self, row: Vec<TableCell>) {
+        self.rows.push(row);
+    }
+
+    pub fn format(&self, indentation: &str) -> String {
+        let mut widths: Vec<usize> = Vec::new();
+        for row in &self.rows {
+            for (i, cell) in row.iter().enumerate() {
+                let width = cell.text.chars().count();
+                if i < widths.len() {
+                    widths[i] = widths[i].max(width);
+                } else {
+                    widths.push(width);
+                }
+            }
+        }
+        let mut text = String::new();
+        for row in &self.rows {
+            let mut line = String::from(indentation);
+            for (i, cell) in row.iter().enumerate() {
+                let padding = " ".repeat(widths[i] - cell.text.chars().count());
+                if i == 0 {
+                    line.push_str(&colorize(&cell.text, cell.color));
+                    line.push_str(&padding);
+                } else {
+                    line.push_str(COLUMN_SEPARATOR);
+                    line.push_str(&padding);
+                    line.push_str(&colorize(&cell.text, cell.color));
+                }
+            }
+            text.push_str(line.trim_end());
+            text.push('\n');
+        }
+        return text;
+    }
+}
+
+impl Default for TextTable {
+    fn default() -> TextTable {
+        return TextTable::new();
+    }
+}
@@ -0,0 +1,163 @@
+use std::collections::BTreeMap;
+use std::ops::Add;
+use chrono::{Datelike, Local, TimeZone, Timelike};
+use crate::analyze::MatchResult;
+use crate::string::{format_percent, INDENTATION_STRING};
+use crate::win_rate_info::WinRateInfo;
+
+/// A break longer than this between two matches starts a new play session
+const SESSION_BREAK_MINUTES: i64 = 60;
+/// Later games of a session are counted together, e.g. "game 6+"
+const SESSION_POSITION_LIMIT: usize = 6;
+/// Longer losing streaks are counted together, e.g. "after 4+ losses"
+const LOSS_STREAK_LIMIT: usize = 4;
+/// Win chance below which I should stop queuing
+const STOP_WIN_CHANCE: f32 = 0.45;
+const WEEKDAY_NAMES: [&str; 7] = ["Monday", "Tuesday", "Wednesday", "Thursday", "Friday", "Saturday", "Sunday"];
+
+/// My win rates by when and how I queue, to find where I tilt
+pub struct TiltInfo {
+    pub count_of_sessions: i32,
+    /// Starting at 1 for the first game of a session
+    win_rates_by_session_position: BTreeMap<usize, WinRateInfo>,
+    /// Count of losses in a row right before the match, within the same session
+    win_rates_by_loss_streak: BTreeMap<usize, WinRateInfo>,
+    /// Local hour of the start of the match
+    win_rates_by_hour: BTreeMap<u32, WinRateInfo>,
+    /// Days since Monday
+    win_rates_by_weekday: BTreeMap<u32, WinRateInfo>,
+}
+
+impl TiltInfo {
+    /// Match results are expected newest first, as the analyzer keeps them
+    pub fn new(match_results: &[MatchResult], significance_threshold: i32) -> TiltInfo {
+        let create_win_rate_info = || WinRateInfo::with_significance_threshold(significance_threshold);
+        let mut tilt_info = TiltInfo {
+            count_of_sessions: 0,
+            win_rates_by_session_position: BTreeMap::new(),
+            win_rates_by_loss_streak: BTreeMap::new(),
+            win_rates_by_hour: BTreeMap::new(),
+            win_rates_by_weekday: BTreeMap::new(),
+        };
+        let mut previous_result: Option<&MatchResult> = None;
+        let mut session_position = 0;
+        let mut loss_streak = 0;
+        for result in match_results.iter().rev() {
+            let is_new_session = match previous_result {
+                Some(previous_result) => {
+                    let previous_end = previous_result.moment + chrono::Duration::seconds(previous_result.game_duration);
+                    result.moment.signed_duration_since(previous_end) > chrono::Duration::minutes(SESSION_BREAK_MINUTES)
+                },
+                None => true,
+            };
+            if is_new_session {
+                tilt_info.count_of_sessions += 1;
+                session_position = 0;
+                loss_streak = 0;
+            }
+            session_position += 1;
+
+            tilt_info.win_rates_by_session_position.entry(session_position.min(SESSION_POSITION_LIMIT))
+                .or_insert_with(create_win_rate_info).add(result.win);
+            tilt_info.win_rates_by_loss_streak.entry(loss_streak.min(LOSS_STREAK_LIMIT))
+                .or_insert_with(create_win_rate_info).add(result.win);
+            let local_moment = Local.from_utc_datetime(&result.moment);
+            tilt_info.win_rates_by_hour.entry(local_moment.hour())
+                .or_insert_with(create_win_rate_info).add(result.win);
+            tilt_info.win_rates_by_weekday.entry(local_moment.weekday().num_days_from_monday())
+                .or_insert_with(create_win_rate_info).add(result.win);
+
+            loss_streak = if result.win { 0 } else { loss_streak + 1 };
+            previous_result = Some(result);
+        }
+        return tilt_info;
+    }
+
+    pub fn get_win_rates_by_session_position(&self) -> &BTreeMap<usize, WinRateInfo> {
+        return &self.win_rates_by_session_position;
+    }
+
+    pub fn get_win_rates_by_loss_streak(&self) -> &BTreeMap<usize, WinRateInfo> {
+        return &self.win_rates_by_loss_streak;
+    }
+
+    pub fn get_win_rates_by_hour(&self) -> &BTreeMap<u32, WinRateInfo> {
+        return &self.win_rates_by_hour;
+    }
+
+    pub fn get_win_rates_by_weekday(&self) -> &BTreeMap<u32, WinRateInfo> {
+        return &self.win_rates_by_weekday;
+    }
+
+    /// When to stop queuing: the shortest significant losing streak or session length with a low win chance
+    pub fn get_recommendation(&self) -> String {
+        let loss_streak = self.win_rates_by_loss_streak.iter()
+            .find(|(streak, info)| **streak > 0 && info.is_significant() && info.get_win_chance() < STOP_WIN_CHANCE);
+        let session_position = self.win_rates_by_session_position.iter()
+            .find(|(position, info)| **position > 1 && info.is_significant() && info.get_win_chance() < STOP_WIN_CHANCE);
+        let mut text = String::new();
+        if let Some((streak, info)) = loss_streak {
+            text = text.add("Stop queuing after ").add(&format_loss_streak(*streak))
+                .add(" in a row: the next game is won with chance ").add(&format_percent(info.get_win_chance()));
+            text.push('\n');
+        }
+        if let Some((position, info)) = session_position {
+            text = text.add("Stop queuing before ").add(&format_session_position(*position))
+                .add(" of a session: it is won with chance ").add(&format_percent(info.get_win_chance()));
+            text.push('\n');
+        }
+        if text.is_empty() {
+            text.push_str("No tilt found: neither losing streaks nor long sessions lower the win chance\n");
+        }
+        return text;
+    }
+
+    pub fn get_summary_text(&self) -> String {
+        let mut text = String::new();
+        text = text.add("count of sessions: ").add(&self.count_of_sessions.to_string());
+        text.push('\n');
+        text = text.add(&format_section("by position in session", self.win_rates_by_session_position.iter()
+            .map(|(position, info)| (format_session_position(*position), info))));
+        text = text.add(&format_section("by losses in a row before", self.win_rates_by_loss_streak.iter()
+            .map(|(streak, info)| (format_loss_streak(*streak), info))));
+        text = text.add(&format_section("by hour of day", self.win_rates_by_hour.iter()
+            .map(|(hour, info)| (format!("{:02}:00", hour), info))));
+        text = text.add(&format_section("by day of week", self.win_rates_by_weekday.iter()
+            .map(|(weekday, info)| (format_weekday(*weekday), info))));
+        text = text.add("recommendation:\n");
+        for line in self.get_recommendation().lines() {
+            text = text.add(INDENTATION_STRING).add(line);
+            text.push('\n');
+        }
+        return text;
+    }
+}
+
+fn format_section<'a>(title: &str, rows: impl Iterator<Item = (String, &'a WinRateInfo)>) -> String {
+    let rows: Vec<(String, &WinRateInfo)> = rows.collect();
+    let named_rows: Vec<(&str, &WinRateInfo)> = rows.iter().map(|(name, info)| (name.as_str(), *info)).collect();
+    let mut text = String::from(title);
+    text.push_str(":\n");
+    text.push_str(&WinRateInfo::format_list_of_named(&named_rows, INDENTATION_STRING));
+    return text;
+}
+
+fn format_session_position(position: usize) -> String {
+    if position >= SESSION_POSITION_LIMIT {
+        return format!("game {}+", position);
+    } else {
+        return format!("game {}", position);
+    }
+}
+
+fn format_loss_streak(streak: usize) -> String {
+    return match streak {
+        1 => String::from("1 loss"),
+        _ if streak >= LOSS_STREAK_LIMIT => format!("{}+ losses", streak),
+        _ => format!("{} losses", streak),
+    };
+}
+
+fn format_weekday(days_from_monday: u32) -> String {
+    return String::from(WEEKDAY_NAMES[days_from_monday as usize % WEEKDAY_NAMES.len()]);
+}
@@ -1,18 +1,26 @@
 use std::ops::Add;
-use crate::string::{ format_percent, format_ratio, format_ratio_detailed };
+use crate::string::{ format_percent, format_ratio, format_ratio_detailed, TableCell, TextColor, TextTable };
 
-const STATISTICAL_SIGNIFICANCE_THRESHOLD: i32 = 5;
+/// Matches needed before a win rate is trusted as it is, unless set otherwise
+pub const DEFAULT_SIGNIFICANCE_THRESHOLD: i32 = 5;
 
 pub struct WinRateInfo {
     count_of_wins: i32,
     count_of_matches: i32,
+    /// Matches needed before the win rate is trusted as it is
+    significance_threshold: i32,
 }
 
 impl WinRateInfo {
     pub fn new() -> WinRateInfo {
+        return WinRateInfo::with_significance_threshold(DEFAULT_SIGNIFICANCE_THRESHOLD);
+    }
+
+    pub fn with_significance_threshold(significance_threshold: i32) -> WinRateInfo {
         return WinRateInfo {
             count_of_wins: 0,
-            count_of_matches: 0
+            count_of_matches: 0,
+            significance_threshold,
         }
     }
 
@@ -32,49 +40,67 @@ impl WinRateInfo {
     }
 
     pub fn get_win_chance(&self) -> f32 {
+        return self.get_win_chance_with_prior(0.5);
+    }
+
+    /// Like `get_win_chance`, but small samples lean towards `prior` instead of a coin flip.
+    pub fn get_win_chance_with_prior(&self, prior: f32) -> f32 {
+        let threshold = self.significance_threshold;
         if self.count_of_matches == 0 {
-            return 0.5
-        } else if self.count_of_matches < STATISTICAL_SIGNIFICANCE_THRESHOLD {
-            let lack = STATISTICAL_SIGNIFICANCE_THRESHOLD - self.count_of_matches;
+            return prior
+        } else if self.count_of_matches < threshold {
+            let lack = threshold - self.count_of_matches;
             let lack = if lack == 1 { 1.3 }
                 else if lack == 2 { 1.6 }
                 else { lack as f32 };
             let win_rate = self.get_win_rate();
-            let delta = win_rate - 0.5;
-            return 0.5 + delta / lack;
+            let delta = win_rate - prior;
+            return prior + delta / lack;
         } else {
             return self.get_win_rate();
         }
     }
 
+    pub fn is_significant(&self) -> bool {
+        return self.count_of_matches >= self.significance_threshold;
+    }
+
     pub fn format_list_of_named(list: &[(&str, &WinRateInfo)], indentation: &str) -> String {
-        let mut text = String::new();
+        let mut table = TextTable::new();
         for (champion_name, win_rate_info) in list {
-            text = text
-                .add(indentation)
-                .add(champion_name)
-                .add(" ")
-                .add(" chance ")
-                .add(&format_percent(win_rate_info.get_win_chance()))
-                .add(" ratio ")
-                .add(&format_ratio(
+            table.add_row(vec![
+                TableCell::new(String::from(*champion_name)),
+                TableCell::colored(
+                    String::from("chance ").add(&format_percent(win_rate_info.get_win_chance())),
+                    TextColor::from_win_chance(win_rate_info.get_win_chance())
+                ),
+                TableCell::new(String::from("ratio ").add(&format_ratio(
                     win_rate_info.count_of_wins,
                     win_rate_info.count_of_matches
-                ))
-                .add(" of ")
-                .add(&win_rate_info.count_of_matches.to_string());
-            text.push('\n');
+                ))),
+                TableCell::new(String::from("of ").add(&win_rate_info.count_of_matches.to_string())),
+            ]);
         }
-        return text;
+        return table.format(indentation);
     }
 
     pub fn get_count_of_matches(&self) -> i32 {
         return self.count_of_matches;
     }
+
+    pub fn get_count_of_wins(&self) -> i32 {
+        return self.count_of_wins;
+    }
+}
+
+impl std::fmt::Display for WinRateInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return f.write_str(&format_ratio_detailed(self.count_of_wins, self.count_of_matches));
+    }
 }
 
-impl ToString for WinRateInfo {
-    fn to_string(&self) -> String {
-        return format_ratio_detailed(self.count_of_wins, self.count_of_matches);
+impl Default for WinRateInfo {
+    fn default() -> WinRateInfo {
+        return WinRateInfo::new();
     }
 }